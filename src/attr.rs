@@ -1,28 +1,108 @@
+use crate::handle::{GuidByteOrder, TraceCallback};
 use crate::types::*;
 use std::ptr;
 
+/// Driver-specific connection attribute for registering a trace callback (see
+/// `crate::handle::TraceEvent`), in the vendor-defined range above
+/// `SQL_ATTR_DRIVER_START` (1200) that the ODBC spec reserves for driver use.
+pub const SQL_ATTR_FURBALL_TRACE_CALLBACK: SQLINTEGER = 1251;
+
+/// Driver-specific connection attribute toggling whether `SQL_C_TYPE_TIMESTAMP`/
+/// `SQL_C_TYPE_DATE`/`SQL_C_TYPE_TIME` retrieval of a `DateTimeOffset` value
+/// applies its zone offset before breaking it into y/m/d/h/m/s (local time) or
+/// leaves it as stored (UTC, the default — so existing callers aren't broken).
+/// `SQL_C_SS_TIMESTAMPOFFSET` is unaffected; it always reports both the
+/// absolute instant and the offset, so there's nothing ambiguous to toggle.
+pub const SQL_ATTR_FURBALL_DATETIMEOFFSET_LOCAL: SQLINTEGER = 1252;
+
+/// Driver-specific statement attribute overriding how many rows `fetch`
+/// pulls ahead into `prefetch_buffer` per round (see `Statement::prefetch_rows`);
+/// a byte budget (not attribute-configurable) also applies on top.
+pub const SQL_ATTR_FURBALL_PREFETCH_ROWS: SQLINTEGER = 1253;
+
+/// Driver-specific connection attribute selecting how `uniqueidentifier`
+/// bytes off the wire are read into `data1`/`data2`/`data3` of `SQL_C_GUID`'s
+/// `SQLGUID` (see `fetch::get_data`'s `SQL_C_GUID` arm and `guid_from_bytes`;
+/// GUIDs parsed from text by `parse_guid` are always read big-endian):
+/// `SQL_FURBALL_GUID_MIXED_ENDIAN` (the default, matching the native
+/// `SQLGUID`/`SQL_GUID` layout on Windows) or `SQL_FURBALL_GUID_RFC4122`.
+pub const SQL_ATTR_FURBALL_GUID_BYTE_ORDER: SQLINTEGER = 1254;
+pub const SQL_FURBALL_GUID_MIXED_ENDIAN: SQLULEN = 0;
+pub const SQL_FURBALL_GUID_RFC4122: SQLULEN = 1;
+
+/// Driver-specific connection attribute naming the encoding (an
+/// `encoding_rs`-recognized label, e.g. `"UTF-8"`, `"SHIFT_JIS"`,
+/// `"ISO-8859-1"`) diagnostic message text is transcoded to before being
+/// handed back through `SQLGetDiagRec` (see
+/// `diagnostics::encode_diag_message`); defaults to UTF-8. `SQLGetDiagField`
+/// returns `SQL_DIAG_MESSAGE_TEXT` as UTF-8 regardless of this attribute.
+pub const SQL_ATTR_FURBALL_CHARSET: SQLINTEGER = 1255;
+
 pub fn set_env_attr(
     env: &mut crate::handle::Environment,
     attribute: SQLINTEGER,
     value: SQLPOINTER,
     _string_length: SQLINTEGER,
 ) -> SQLRETURN {
+    env.diagnostics.clear();
     match attribute {
         SQL_ATTR_ODBC_VERSION => {
-            env.odbc_version = value as SQLINTEGER;
-            SQL_SUCCESS
+            let version = value as SQLINTEGER;
+            match version {
+                SQL_OV_ODBC2 | SQL_OV_ODBC3 | SQL_OV_ODBC3_80 => {
+                    env.odbc_version = version;
+                    SQL_SUCCESS
+                }
+                _ => {
+                    env.diagnostics.push(crate::handle::DiagRecord {
+                        state: "HY024".to_string(),
+                        native_error: 0,
+                        message: format!("Invalid ODBC version attribute value {}", version),
+                    });
+                    SQL_ERROR
+                }
+            }
         }
         _ => SQL_SUCCESS, // ignore unknown
     }
 }
 
+/// Reads a (possibly non-NTS) string attribute value the same way the spec's
+/// `SQLSetConnectAttr`/`SQLSetStmtAttr` string attributes are passed: a byte
+/// pointer plus either an explicit length or `SQL_NTS`.
+fn read_attr_string(ptr: *const u8, length: SQLINTEGER) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let slice = if length == SQL_NTS as SQLINTEGER || length < 0 {
+        let mut end = 0;
+        unsafe {
+            while *ptr.add(end) != 0 {
+                end += 1;
+            }
+        }
+        unsafe { std::slice::from_raw_parts(ptr, end) }
+    } else {
+        unsafe { std::slice::from_raw_parts(ptr, length as usize) }
+    };
+    String::from_utf8_lossy(slice).to_string()
+}
+
 pub fn set_connect_attr(
     conn: &mut crate::handle::Connection,
     attribute: SQLINTEGER,
     value: SQLPOINTER,
-    _string_length: SQLINTEGER,
+    string_length: SQLINTEGER,
 ) -> SQLRETURN {
     match attribute {
+        SQL_ATTR_TRACE => {
+            crate::trace::set_trace(value as SQLULEN == SQL_OPT_TRACE_ON as SQLULEN);
+            SQL_SUCCESS
+        }
+        SQL_ATTR_TRACEFILE => {
+            crate::trace::set_tracefile(read_attr_string(value as *const u8, string_length));
+            SQL_SUCCESS
+        }
         SQL_ATTR_AUTOCOMMIT => {
             let val = value as SQLULEN;
             let new_autocommit = val != 0; // SQL_AUTOCOMMIT_OFF = 0
@@ -37,7 +117,17 @@ pub fn set_connect_attr(
                                 .await
                                 .map_err(|e| e.to_string())
                         });
-                        if let Err(_) = result {
+                        if let Err(msg) = result {
+                            let native = crate::execute::extract_error_number(&msg);
+                            let state = crate::execute::sqlstate_for_native_error(
+                                native,
+                                crate::execute::extract_severity(&msg),
+                            );
+                            conn.diagnostics.push(crate::handle::DiagRecord {
+                                state: state.to_string(),
+                                native_error: native,
+                                message: msg,
+                            });
                             return SQL_ERROR;
                         }
                         conn.in_transaction = false;
@@ -47,7 +137,114 @@ pub fn set_connect_attr(
             }
             SQL_SUCCESS
         }
-        SQL_ATTR_LOGIN_TIMEOUT | SQL_ATTR_CONNECTION_TIMEOUT => SQL_SUCCESS,
+        SQL_ATTR_LOGIN_TIMEOUT => {
+            conn.login_timeout_secs = value as SQLULEN as u32;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_CONNECTION_TIMEOUT => {
+            conn.connection_timeout_secs = value as SQLULEN as u32;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_TXN_ISOLATION => {
+            let level = value as SQLINTEGER;
+            let level_sql = match level {
+                SQL_TXN_READ_UNCOMMITTED => "READ UNCOMMITTED",
+                SQL_TXN_READ_COMMITTED => "READ COMMITTED",
+                SQL_TXN_REPEATABLE_READ => "REPEATABLE READ",
+                SQL_TXN_SERIALIZABLE => "SERIALIZABLE",
+                SQL_TXN_SS_SNAPSHOT => "SNAPSHOT",
+                _ => {
+                    conn.diagnostics.push(crate::handle::DiagRecord {
+                        state: "HYC00".to_string(),
+                        native_error: 0,
+                        message: "Isolation level not supported".to_string(),
+                    });
+                    return SQL_ERROR;
+                }
+            };
+            if let Some(client) = conn.client.as_mut() {
+                let sql = format!("SET TRANSACTION ISOLATION LEVEL {}", level_sql);
+                let result = crate::runtime::block_on(async {
+                    let mut w = crate::handle::StringRowWriter::new();
+                    client.batch_into(sql, &mut w).await.map_err(|e| e.to_string())
+                });
+                if let Err(msg) = result {
+                    let native = crate::execute::extract_error_number(&msg);
+                    let state = crate::execute::sqlstate_for_native_error(
+                        native,
+                        crate::execute::extract_severity(&msg),
+                    );
+                    conn.diagnostics.push(crate::handle::DiagRecord {
+                        state: state.to_string(),
+                        native_error: native,
+                        message: msg,
+                    });
+                    return SQL_ERROR;
+                }
+            }
+            conn.isolation_level = level;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ACCESS_MODE => {
+            let read_only = value as SQLUINTEGER == SQL_MODE_READ_ONLY as SQLUINTEGER;
+            if let Some(client) = conn.client.as_mut() {
+                let sql = if read_only {
+                    "SET TRANSACTION READ ONLY"
+                } else {
+                    "SET TRANSACTION READ WRITE"
+                };
+                let result = crate::runtime::block_on(async {
+                    let mut w = crate::handle::StringRowWriter::new();
+                    client.batch_into(sql, &mut w).await.map_err(|e| e.to_string())
+                });
+                if let Err(msg) = result {
+                    let native = crate::execute::extract_error_number(&msg);
+                    let state = crate::execute::sqlstate_for_native_error(
+                        native,
+                        crate::execute::extract_severity(&msg),
+                    );
+                    conn.diagnostics.push(crate::handle::DiagRecord {
+                        state: state.to_string(),
+                        native_error: native,
+                        message: msg,
+                    });
+                    return SQL_ERROR;
+                }
+            }
+            conn.read_only = read_only;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_TRACE_CALLBACK => {
+            // `value` is a raw pointer to a boxed `TraceCallback`, handed off
+            // by the caller (e.g. `Box::into_raw(Box::new(callback))` cast to
+            // `SQLPOINTER`); we take ownership of it here. A null pointer
+            // clears any previously registered callback.
+            conn.trace_callback = if value.is_null() {
+                None
+            } else {
+                Some(*unsafe { Box::from_raw(value as *mut TraceCallback) })
+            };
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_DATETIMEOFFSET_LOCAL => {
+            conn.normalize_timestampoffset_local = value as SQLULEN != 0;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_GUID_BYTE_ORDER => {
+            conn.guid_byte_order = if value as SQLULEN == SQL_FURBALL_GUID_RFC4122 {
+                GuidByteOrder::Rfc4122
+            } else {
+                GuidByteOrder::Mixed
+            };
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_CHARSET => {
+            let charset = read_attr_string(value as *const u8, string_length);
+            if !charset.is_empty() {
+                conn.charset = charset;
+            }
+            SQL_SUCCESS
+        }
         _ => SQL_SUCCESS,
     }
 }
@@ -155,27 +352,200 @@ pub fn get_info(
         SQL_DEFAULT_TXN_ISOLATION => write_u32(2), // READ_COMMITTED
         SQL_SUBQUERIES => write_u32(0x1F),
         SQL_UNION => write_u32(3),
+        // Forward-only is the streaming default (see Statement::cursor_type);
+        // static is the buffered mode SQLFetchScroll's non-NEXT orientations
+        // operate over (see fetch::fetch_scroll).
+        SQL_SCROLL_OPTIONS => write_u32(SQL_SO_FORWARD_ONLY | SQL_SO_STATIC),
         _ => write_str(""),
     }
 }
 
 pub fn set_stmt_attr(
-    _stmt: &mut crate::handle::Statement,
-    _attribute: SQLINTEGER,
-    _value: SQLPOINTER,
+    stmt: &mut crate::handle::Statement,
+    attribute: SQLINTEGER,
+    value: SQLPOINTER,
     _string_length: SQLINTEGER,
 ) -> SQLRETURN {
-    SQL_SUCCESS
+    match attribute {
+        SQL_ATTR_CURSOR_TYPE => {
+            stmt.cursor_type = value as SQLINTEGER;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROW_ARRAY_SIZE => {
+            stmt.row_array_size = std::cmp::max(1, value as SQLULEN);
+            SQL_SUCCESS
+        }
+        SQL_ATTR_QUERY_TIMEOUT => {
+            stmt.query_timeout_secs = value as SQLULEN as u32;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_MAX_ROWS => {
+            stmt.max_rows = value as SQLULEN;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROW_BIND_TYPE => {
+            stmt.row_bind_type = value as SQLULEN;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_PREFETCH_ROWS => {
+            stmt.prefetch_rows = std::cmp::max(1, value as SQLULEN);
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROW_STATUS_PTR => {
+            stmt.row_status_ptr = value as *mut SQLUSMALLINT;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROWS_FETCHED_PTR => {
+            stmt.rows_fetched_ptr = value as *mut SQLULEN;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_PARAMSET_SIZE => {
+            stmt.paramset_size = std::cmp::max(1, value as usize);
+            SQL_SUCCESS
+        }
+        SQL_ATTR_PARAM_STATUS_PTR => {
+            stmt.param_status_ptr = value as *mut SQLUSMALLINT;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_PARAMS_PROCESSED_PTR => {
+            stmt.params_processed_ptr = value as *mut SQLULEN;
+            SQL_SUCCESS
+        }
+        SQL_ATTR_CONCURRENCY => {
+            let requested = value as SQLINTEGER;
+            if requested != SQL_CONCUR_READ_ONLY {
+                // No updatable-cursor support; silently claiming otherwise
+                // would let callers issue SQLSetPos updates that do nothing.
+                stmt.concurrency = SQL_CONCUR_READ_ONLY;
+                stmt.diagnostics.push(crate::handle::DiagRecord {
+                    state: "01S02".to_string(),
+                    native_error: 0,
+                    message: "Option value changed: only SQL_CONCUR_READ_ONLY is supported"
+                        .to_string(),
+                });
+                return SQL_SUCCESS_WITH_INFO;
+            }
+            stmt.concurrency = requested;
+            SQL_SUCCESS
+        }
+        _ => SQL_SUCCESS,
+    }
 }
 
 pub fn get_stmt_attr(
-    _stmt: &crate::handle::Statement,
-    _attribute: SQLINTEGER,
-    _value: SQLPOINTER,
+    stmt: &crate::handle::Statement,
+    attribute: SQLINTEGER,
+    value: SQLPOINTER,
     _buffer_length: SQLINTEGER,
-    _string_length: *mut SQLINTEGER,
+    string_length: *mut SQLINTEGER,
 ) -> SQLRETURN {
-    SQL_SUCCESS
+    match attribute {
+        SQL_ATTR_CURSOR_TYPE => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLINTEGER) = stmt.cursor_type;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLINTEGER>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROW_ARRAY_SIZE => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.row_array_size;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_QUERY_TIMEOUT => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.query_timeout_secs as SQLULEN;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_PARAMSET_SIZE => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.paramset_size as SQLULEN;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_MAX_ROWS => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.max_rows;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_CONCURRENCY => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLINTEGER) = stmt.concurrency;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLINTEGER>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_ROW_BIND_TYPE => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.row_bind_type;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_ATTR_FURBALL_PREFETCH_ROWS => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLULEN) = stmt.prefetch_rows;
+                }
+            }
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = std::mem::size_of::<SQLULEN>() as SQLINTEGER;
+                }
+            }
+            SQL_SUCCESS
+        }
+        _ => SQL_SUCCESS,
+    }
 }
 
 pub fn get_info_w(
@@ -284,6 +654,10 @@ pub fn get_info_w(
         SQL_DEFAULT_TXN_ISOLATION => write_u32(2),
         SQL_SUBQUERIES => write_u32(0x1F),
         SQL_UNION => write_u32(3),
+        // Forward-only is the streaming default (see Statement::cursor_type);
+        // static is the buffered mode SQLFetchScroll's non-NEXT orientations
+        // operate over (see fetch::fetch_scroll).
+        SQL_SCROLL_OPTIONS => write_u32(SQL_SO_FORWARD_ONLY | SQL_SO_STATIC),
         _ => write_str_w(""),
     }
 }
@@ -2,74 +2,179 @@ use crate::execute;
 use crate::handle::*;
 use crate::types::*;
 
-pub fn get_type_info(stmt: &mut Statement, data_type: SQLSMALLINT) -> SQLRETURN {
-    let _filter = if data_type == SQL_ALL_TYPES {
-        String::new()
+/// Quote a string as a T-SQL Unicode string literal (`N'...'`), doubling
+/// embedded `'` — the one escaping rule every predicate built in this module
+/// needs for schema/table/column filter values.
+fn quote_literal(s: &str) -> String {
+    format!("N'{}'", s.replace('\'', "''"))
+}
+
+/// Bracket-quote a SQL Server identifier (doubling embedded `]`), but only
+/// when it isn't already a plain, unambiguous identifier — mirroring how
+/// mature engines skip quoting non-reserved, regularly-shaped names to keep
+/// generated SQL readable.
+#[allow(dead_code)]
+fn quote_ident(s: &str) -> String {
+    let is_plain = s
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_plain {
+        s.to_string()
     } else {
-        format!("WHERE DATA_TYPE = {}", data_type)
+        format!("[{}]", s.replace(']', "]]"))
+    }
+}
+
+/// One synthesized `SQLGetTypeInfo` row, one per SQL Server type this driver
+/// knows about — the same enumeration `lib.rs`'s `SQL_DESC_TYPE_NAME` match
+/// uses, so the two stay in lockstep if a type is ever added or removed.
+struct TypeInfoRow {
+    name: &'static str,
+    data_type: SQLSMALLINT,
+    column_size: SQLINTEGER,
+    literal_quote: Option<&'static str>,
+    create_params: Option<&'static str>,
+    unsigned: bool,
+    minimum_scale: SQLSMALLINT,
+    maximum_scale: SQLSMALLINT,
+    sql_data_type: SQLSMALLINT,
+    sql_datetime_sub: Option<SQLSMALLINT>,
+    num_prec_radix: Option<SQLINTEGER>,
+}
+
+const TYPE_INFO_ROWS: &[TypeInfoRow] = &[
+    TypeInfoRow { name: "int", data_type: 4, column_size: 10, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 4, sql_datetime_sub: None, num_prec_radix: Some(10) },
+    TypeInfoRow { name: "smallint", data_type: 5, column_size: 5, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 5, sql_datetime_sub: None, num_prec_radix: Some(10) },
+    TypeInfoRow { name: "tinyint", data_type: -6, column_size: 3, literal_quote: None, create_params: None, unsigned: true, minimum_scale: 0, maximum_scale: 0, sql_data_type: -6, sql_datetime_sub: None, num_prec_radix: Some(10) },
+    TypeInfoRow { name: "bigint", data_type: -5, column_size: 19, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -5, sql_datetime_sub: None, num_prec_radix: Some(10) },
+    TypeInfoRow { name: "bit", data_type: -7, column_size: 1, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -7, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "float", data_type: 8, column_size: 53, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 8, sql_datetime_sub: None, num_prec_radix: Some(2) },
+    TypeInfoRow { name: "real", data_type: 7, column_size: 24, literal_quote: None, create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 7, sql_datetime_sub: None, num_prec_radix: Some(2) },
+    TypeInfoRow { name: "varchar", data_type: 12, column_size: 2147483647, literal_quote: Some("'"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 12, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "char", data_type: 1, column_size: 8000, literal_quote: Some("'"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 1, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "nvarchar", data_type: -9, column_size: 1073741823, literal_quote: Some("'"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -9, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "nchar", data_type: -8, column_size: 4000, literal_quote: Some("'"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -8, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "datetime", data_type: 93, column_size: 23, literal_quote: Some("'"), create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 3, sql_data_type: 9, sql_datetime_sub: Some(3), num_prec_radix: None },
+    TypeInfoRow { name: "date", data_type: 91, column_size: 10, literal_quote: Some("'"), create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: 9, sql_datetime_sub: Some(1), num_prec_radix: None },
+    TypeInfoRow { name: "time", data_type: 92, column_size: 16, literal_quote: Some("'"), create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 7, sql_data_type: 9, sql_datetime_sub: Some(2), num_prec_radix: None },
+    TypeInfoRow { name: "decimal", data_type: 3, column_size: 38, literal_quote: None, create_params: Some("precision,scale"), unsigned: false, minimum_scale: 0, maximum_scale: 38, sql_data_type: 3, sql_datetime_sub: None, num_prec_radix: Some(10) },
+    TypeInfoRow { name: "binary", data_type: -2, column_size: 8000, literal_quote: Some("0x"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -2, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "varbinary", data_type: -3, column_size: 2147483647, literal_quote: Some("0x"), create_params: Some("max length"), unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -3, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "uniqueidentifier", data_type: -11, column_size: 36, literal_quote: Some("'"), create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -11, sql_datetime_sub: None, num_prec_radix: None },
+    TypeInfoRow { name: "xml", data_type: -152, column_size: 0, literal_quote: Some("'"), create_params: None, unsigned: false, minimum_scale: 0, maximum_scale: 0, sql_data_type: -152, sql_datetime_sub: None, num_prec_radix: None },
+];
+
+fn type_info_columns() -> Vec<ColumnDesc> {
+    let col = |name: &str, sql_type: SQLSMALLINT, size: SQLULEN, nullable: SQLSMALLINT| ColumnDesc {
+        name: name.to_string(),
+        sql_type,
+        size,
+        decimal_digits: 0,
+        nullable,
+        ss_type: None,
+        base_table: String::new(),
+        base_column: String::new(),
+        schema: String::new(),
+        catalog: String::new(),
     };
+    vec![
+        col("TYPE_NAME", SQL_VARCHAR, 128, SQL_NO_NULLS),
+        col("DATA_TYPE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("COLUMN_SIZE", SQL_INTEGER, 10, SQL_NULLABLE),
+        col("LITERAL_PREFIX", SQL_VARCHAR, 128, SQL_NULLABLE),
+        col("LITERAL_SUFFIX", SQL_VARCHAR, 128, SQL_NULLABLE),
+        col("CREATE_PARAMS", SQL_VARCHAR, 128, SQL_NULLABLE),
+        col("NULLABLE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("CASE_SENSITIVE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("SEARCHABLE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("UNSIGNED_ATTRIBUTE", SQL_SMALLINT, 5, SQL_NULLABLE),
+        col("FIXED_PREC_SCALE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("AUTO_UNIQUE_VALUE", SQL_SMALLINT, 5, SQL_NULLABLE),
+        col("LOCAL_TYPE_NAME", SQL_VARCHAR, 128, SQL_NULLABLE),
+        col("MINIMUM_SCALE", SQL_SMALLINT, 5, SQL_NULLABLE),
+        col("MAXIMUM_SCALE", SQL_SMALLINT, 5, SQL_NULLABLE),
+        col("SQL_DATA_TYPE", SQL_SMALLINT, 5, SQL_NO_NULLS),
+        col("SQL_DATETIME_SUB", SQL_SMALLINT, 5, SQL_NULLABLE),
+        col("NUM_PREC_RADIX", SQL_INTEGER, 10, SQL_NULLABLE),
+        col("INTERVAL_PRECISION", SQL_SMALLINT, 5, SQL_NULLABLE),
+    ]
+}
 
-    // Return a standard ODBC type catalog
-    let sql = format!(
-        "SELECT \
-         TYPE_NAME = tp.name, \
-         DATA_TYPE = CASE tp.name \
-           WHEN 'int' THEN 4 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
-           WHEN 'bigint' THEN -5 WHEN 'float' THEN 8 WHEN 'real' THEN 7 \
-           WHEN 'bit' THEN -7 WHEN 'datetime' THEN 93 WHEN 'datetime2' THEN 93 \
-           WHEN 'date' THEN 91 WHEN 'time' THEN 92 \
-           WHEN 'varchar' THEN 12 WHEN 'nvarchar' THEN -9 \
-           WHEN 'char' THEN 1 WHEN 'nchar' THEN -8 \
-           WHEN 'text' THEN -1 WHEN 'ntext' THEN -10 \
-           WHEN 'binary' THEN -2 WHEN 'varbinary' THEN -3 WHEN 'image' THEN -4 \
-           WHEN 'decimal' THEN 3 WHEN 'numeric' THEN 2 \
-           WHEN 'money' THEN 3 WHEN 'smallmoney' THEN 3 \
-           WHEN 'uniqueidentifier' THEN -11 \
-           WHEN 'xml' THEN -10 \
-           ELSE 12 END, \
-         COLUMN_SIZE = CASE \
-           WHEN tp.name IN ('int') THEN 10 \
-           WHEN tp.name IN ('smallint') THEN 5 \
-           WHEN tp.name IN ('tinyint') THEN 3 \
-           WHEN tp.name IN ('bigint') THEN 19 \
-           WHEN tp.name IN ('float') THEN 53 \
-           WHEN tp.name IN ('real') THEN 24 \
-           WHEN tp.name IN ('bit') THEN 1 \
-           WHEN tp.name IN ('datetime','datetime2') THEN 23 \
-           WHEN tp.name IN ('date') THEN 10 \
-           WHEN tp.name IN ('time') THEN 16 \
-           WHEN tp.name IN ('uniqueidentifier') THEN 36 \
-           ELSE tp.max_length END, \
-         LITERAL_PREFIX = CASE WHEN tp.name IN ('varchar','nvarchar','char','nchar','text','ntext','datetime','datetime2','date','time','uniqueidentifier') THEN '''' WHEN tp.name IN ('binary','varbinary','image') THEN '0x' ELSE NULL END, \
-         LITERAL_SUFFIX = CASE WHEN tp.name IN ('varchar','nvarchar','char','nchar','text','ntext','datetime','datetime2','date','time','uniqueidentifier') THEN '''' ELSE NULL END, \
-         CREATE_PARAMS = CASE WHEN tp.name IN ('varchar','nvarchar','char','nchar','binary','varbinary') THEN 'max length' WHEN tp.name IN ('decimal','numeric') THEN 'precision,scale' ELSE NULL END, \
-         NULLABLE = CAST(1 AS SMALLINT), \
-         CASE_SENSITIVE = CAST(0 AS SMALLINT), \
-         SEARCHABLE = CAST(3 AS SMALLINT), \
-         UNSIGNED_ATTRIBUTE = CASE WHEN tp.name IN ('tinyint') THEN CAST(1 AS SMALLINT) ELSE CAST(0 AS SMALLINT) END, \
-         FIXED_PREC_SCALE = CASE WHEN tp.name IN ('money','smallmoney') THEN CAST(1 AS SMALLINT) ELSE CAST(0 AS SMALLINT) END, \
-         AUTO_UNIQUE_VALUE = CAST(0 AS SMALLINT), \
-         LOCAL_TYPE_NAME = tp.name, \
-         MINIMUM_SCALE = CAST(0 AS SMALLINT), \
-         MAXIMUM_SCALE = CASE WHEN tp.name IN ('decimal','numeric') THEN CAST(38 AS SMALLINT) WHEN tp.name IN ('datetime2','time') THEN CAST(7 AS SMALLINT) ELSE CAST(0 AS SMALLINT) END, \
-         SQL_DATA_TYPE = CAST(0 AS SMALLINT), \
-         SQL_DATETIME_SUB = CAST(NULL AS SMALLINT), \
-         NUM_PREC_RADIX = CASE WHEN tp.name IN ('int','smallint','tinyint','bigint','decimal','numeric','money','smallmoney') THEN 10 WHEN tp.name IN ('float','real') THEN 2 ELSE NULL END, \
-         INTERVAL_PRECISION = CAST(NULL AS SMALLINT) \
-         FROM sys.types tp WHERE tp.system_type_id = tp.user_type_id \
-         ORDER BY DATA_TYPE"
-    );
+fn type_info_row(row: &TypeInfoRow) -> Vec<CellValue> {
+    vec![
+        CellValue::String(row.name.to_string()),
+        CellValue::I16(row.data_type),
+        CellValue::I32(row.column_size),
+        match row.literal_quote {
+            Some(q) => CellValue::String(q.to_string()),
+            None => CellValue::Null,
+        },
+        match row.literal_quote {
+            Some("0x") => CellValue::Null,
+            Some(q) => CellValue::String(q.to_string()),
+            None => CellValue::Null,
+        },
+        match row.create_params {
+            Some(p) => CellValue::String(p.to_string()),
+            None => CellValue::Null,
+        },
+        CellValue::I16(1), // SQL_NULLABLE: nullability isn't known without a concrete column
+        CellValue::I16(0), // CASE_SENSITIVE
+        CellValue::I16(3), // SQL_SEARCHABLE
+        CellValue::I16(row.unsigned as i16),
+        CellValue::I16(0), // FIXED_PREC_SCALE
+        CellValue::I16(0), // AUTO_UNIQUE_VALUE
+        CellValue::String(row.name.to_string()),
+        CellValue::I16(row.minimum_scale),
+        CellValue::I16(row.maximum_scale),
+        CellValue::I16(row.sql_data_type),
+        match row.sql_datetime_sub {
+            Some(v) => CellValue::I16(v),
+            None => CellValue::Null,
+        },
+        match row.num_prec_radix {
+            Some(v) => CellValue::I32(v),
+            None => CellValue::Null,
+        },
+        CellValue::Null, // INTERVAL_PRECISION: no interval types supported
+    ]
+}
 
-    execute::exec_direct(stmt, &sql)
+/// Synthesizes the standard 19-column `SQLGetTypeInfo` result set entirely
+/// in-memory, one row per SQL Server type, rather than probing a live
+/// connection — the catalog is static driver knowledge, not something that
+/// varies per server. When `data_type` isn't `SQL_ALL_TYPES`, only the
+/// matching rows are returned, sorted by `DATA_TYPE` like the real catalog
+/// view.
+pub fn get_type_info(stmt: &mut Statement, data_type: SQLSMALLINT) -> SQLRETURN {
+    let mut rows: Vec<&TypeInfoRow> = TYPE_INFO_ROWS
+        .iter()
+        .filter(|r| data_type == SQL_ALL_TYPES || r.data_type == data_type)
+        .collect();
+    rows.sort_by_key(|r| r.data_type);
+
+    stmt.columns = type_info_columns();
+    stmt.rows = rows.into_iter().map(type_info_row).collect();
+    stmt.row_count = stmt.rows.len() as SQLLEN;
+    stmt.pending_result_sets = Vec::new();
+    stmt.row_index = -1;
+    stmt.executed = true;
+    stmt.streaming = false;
+    stmt.read_offsets.clear();
+    stmt.current_row.clear();
+    stmt.rows_fetched = 0;
+    SQL_SUCCESS
 }
 
 pub fn primary_keys(stmt: &mut Statement, _catalog: &str, schema: &str, table: &str) -> SQLRETURN {
     let mut conditions = vec!["1=1".to_string()];
     if !table.is_empty() {
-        conditions.push(format!("t.name = N'{}'", table.replace('\'', "''")));
+        conditions.push(format!("t.name = {}", quote_literal(table)));
     }
     if !schema.is_empty() {
-        conditions.push(format!("s.name = N'{}'", schema.replace('\'', "''")));
+        conditions.push(format!("s.name = {}", quote_literal(schema)));
     }
 
     let sql = format!(
@@ -87,6 +192,64 @@ pub fn primary_keys(stmt: &mut Statement, _catalog: &str, schema: &str, table: &
     execute::exec_direct(stmt, &sql)
 }
 
+/// Per-column generation metadata (identity/computed), akin to the
+/// INFORMATION_SCHEMA-style identity/computed-column reports schema-scripting
+/// tools rely on. Not a standard ODBC catalog function; merge on TABLE_CAT/
+/// TABLE_SCHEM/TABLE_NAME/COLUMN_NAME against SQLColumns output.
+pub fn generation_columns(stmt: &mut Statement, _catalog: &str, schema: &str, table: &str) -> SQLRETURN {
+    let mut conditions = vec!["1=1".to_string()];
+    if !table.is_empty() {
+        conditions.push(format!("t.name = {}", quote_literal(table)));
+    }
+    if !schema.is_empty() {
+        conditions.push(format!("s.name = {}", quote_literal(schema)));
+    }
+
+    let sql = format!(
+        "SELECT DB_NAME() AS TABLE_CAT, s.name AS TABLE_SCHEM, t.name AS TABLE_NAME, \
+         c.name AS COLUMN_NAME, \
+         CASE WHEN c.is_identity = 1 THEN 1 ELSE 0 END AS IS_IDENTITY, \
+         ic.seed_value AS IDENTITY_SEED, \
+         ic.increment_value AS IDENTITY_INCREMENT, \
+         CASE WHEN c.is_computed = 1 THEN 1 ELSE 0 END AS IS_COMPUTED, \
+         cc.definition AS GENERATION_EXPRESSION \
+         FROM sys.columns c \
+         JOIN sys.tables t ON c.object_id = t.object_id \
+         JOIN sys.schemas s ON t.schema_id = s.schema_id \
+         LEFT JOIN sys.identity_columns ic ON c.object_id = ic.object_id AND c.column_id = ic.column_id \
+         LEFT JOIN sys.computed_columns cc ON c.object_id = cc.object_id AND c.column_id = cc.column_id \
+         WHERE {} \
+         ORDER BY TABLE_SCHEM, TABLE_NAME, c.column_id",
+        conditions.join(" AND ")
+    );
+    execute::exec_direct(stmt, &sql)
+}
+
+pub fn procedures(stmt: &mut Statement, _catalog: &str, schema: &str, proc: &str) -> SQLRETURN {
+    let mut conditions = vec!["1=1".to_string()];
+    if !proc.is_empty() {
+        conditions.push(format!("p.name LIKE {}", quote_literal(proc)));
+    }
+    if !schema.is_empty() {
+        conditions.push(format!("s.name LIKE {}", quote_literal(schema)));
+    }
+
+    let sql = format!(
+        "SELECT DB_NAME() AS PROCEDURE_CAT, s.name AS PROCEDURE_SCHEM, p.name AS PROCEDURE_NAME, \
+         CAST(NULL AS INT) AS NUM_INPUT_PARAMS, \
+         CAST(NULL AS INT) AS NUM_OUTPUT_PARAMS, \
+         CAST(NULL AS INT) AS NUM_RESULT_SETS, \
+         CAST(NULL AS NVARCHAR(1)) AS REMARKS, \
+         CASE WHEN p.type = 'P' OR p.type = 'PC' THEN 1 ELSE 2 END AS PROCEDURE_TYPE \
+         FROM sys.objects p \
+         JOIN sys.schemas s ON p.schema_id = s.schema_id \
+         WHERE p.type IN ('P', 'PC', 'FN', 'TF', 'IF') AND {} \
+         ORDER BY PROCEDURE_SCHEM, PROCEDURE_NAME",
+        conditions.join(" AND ")
+    );
+    execute::exec_direct(stmt, &sql)
+}
+
 pub fn statistics(
     stmt: &mut Statement,
     _catalog: &str,
@@ -103,29 +266,51 @@ pub fn statistics(
 
     let mut conditions = vec!["1=1".to_string()];
     if !table.is_empty() {
-        conditions.push(format!("t.name = N'{}'", table.replace('\'', "''")));
+        conditions.push(format!("t.name = {}", quote_literal(table)));
     }
     if !schema.is_empty() {
-        conditions.push(format!("s.name = N'{}'", schema.replace('\'', "''")));
+        conditions.push(format!("s.name = {}", quote_literal(schema)));
     }
 
     let sql = format!(
+        // The leading SQL_TABLE_STAT row (TYPE = 0) carries the table's own
+        // cardinality/page count and sorts ahead of the index rows because
+        // its NON_UNIQUE/TYPE are NULL/0, which order first in the ORDER BY.
         "SELECT DB_NAME() AS TABLE_CAT, s.name AS TABLE_SCHEM, t.name AS TABLE_NAME, \
+         CAST(NULL AS SMALLINT) AS NON_UNIQUE, \
+         CAST(NULL AS VARCHAR(128)) AS INDEX_QUALIFIER, \
+         CAST(NULL AS VARCHAR(128)) AS INDEX_NAME, \
+         CAST(0 AS SMALLINT) AS TYPE, \
+         CAST(NULL AS SMALLINT) AS ORDINAL_POSITION, \
+         CAST(NULL AS VARCHAR(128)) AS COLUMN_NAME, \
+         CAST(NULL AS VARCHAR(1)) AS ASC_OR_DESC, \
+         (SELECT SUM(ps.row_count) FROM sys.dm_db_partition_stats ps \
+          WHERE ps.object_id = t.object_id AND ps.index_id IN (0, 1)) AS CARDINALITY, \
+         (SELECT SUM(ps.used_page_count) FROM sys.dm_db_partition_stats ps \
+          WHERE ps.object_id = t.object_id AND ps.index_id IN (0, 1)) AS PAGES, \
+         CAST(NULL AS VARCHAR(1)) AS FILTER_CONDITION \
+         FROM sys.tables t \
+         JOIN sys.schemas s ON t.schema_id = s.schema_id \
+         WHERE {0} \
+         UNION ALL \
+         SELECT DB_NAME() AS TABLE_CAT, s.name AS TABLE_SCHEM, t.name AS TABLE_NAME, \
          CASE WHEN i.is_unique = 1 THEN 0 ELSE 1 END AS NON_UNIQUE, \
          DB_NAME() AS INDEX_QUALIFIER, i.name AS INDEX_NAME, \
          CASE WHEN i.type_desc = 'CLUSTERED' THEN 1 ELSE 3 END AS TYPE, \
          ic.key_ordinal AS ORDINAL_POSITION, \
          c.name AS COLUMN_NAME, \
          CASE WHEN ic.is_descending_key = 1 THEN 'D' ELSE 'A' END AS ASC_OR_DESC, \
-         CAST(NULL AS INT) AS CARDINALITY, \
-         CAST(NULL AS INT) AS PAGES, \
+         (SELECT SUM(ps.row_count) FROM sys.dm_db_partition_stats ps \
+          WHERE ps.object_id = i.object_id AND ps.index_id = i.index_id) AS CARDINALITY, \
+         (SELECT SUM(ps.used_page_count) FROM sys.dm_db_partition_stats ps \
+          WHERE ps.object_id = i.object_id AND ps.index_id = i.index_id) AS PAGES, \
          CAST(NULL AS VARCHAR(1)) AS FILTER_CONDITION \
          FROM sys.indexes i \
          JOIN sys.index_columns ic ON i.object_id = ic.object_id AND i.index_id = ic.index_id \
          JOIN sys.columns c ON ic.object_id = c.object_id AND ic.column_id = c.column_id \
          JOIN sys.tables t ON i.object_id = t.object_id \
          JOIN sys.schemas s ON t.schema_id = s.schema_id \
-         WHERE {} {} AND i.type > 0 \
+         WHERE {0} {1} AND i.type > 0 \
          ORDER BY NON_UNIQUE, TYPE, INDEX_NAME, ORDINAL_POSITION",
         conditions.join(" AND "),
         unique_filter
@@ -142,37 +327,88 @@ pub fn special_columns(
 ) -> SQLRETURN {
     let mut conditions = vec!["1=1".to_string()];
     if !table.is_empty() {
-        conditions.push(format!("t.name = N'{}'", table.replace('\'', "''")));
+        conditions.push(format!("t.name = {}", quote_literal(table)));
     }
     if !schema.is_empty() {
-        conditions.push(format!("s.name = N'{}'", schema.replace('\'', "''")));
+        conditions.push(format!("s.name = {}", quote_literal(schema)));
     }
 
-    // SQL_BEST_ROWID = 1 (identity columns), SQL_ROWVER = 2 (timestamp/rowversion)
-    let extra_filter = if id_type == 2 {
-        "AND tp.name IN ('timestamp','rowversion')"
-    } else {
-        "AND c.is_identity = 1"
-    };
+    // SQL_ROWVER = 2: timestamp/rowversion columns, unchanged by the
+    // best-rowid fallback chain below.
+    if id_type == 2 {
+        let sql = format!(
+            "SELECT CAST(2 AS SMALLINT) AS SCOPE, c.name AS COLUMN_NAME, \
+             CAST(-2 AS SMALLINT) AS DATA_TYPE, \
+             tp.name AS TYPE_NAME, \
+             COALESCE(c.max_length, 0) AS COLUMN_SIZE, \
+             COALESCE(c.max_length, 0) AS BUFFER_LENGTH, \
+             c.scale AS DECIMAL_DIGITS, \
+             CAST(1 AS SMALLINT) AS PSEUDO_COLUMN \
+             FROM sys.columns c \
+             JOIN sys.tables t ON c.object_id = t.object_id \
+             JOIN sys.schemas s ON t.schema_id = s.schema_id \
+             JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND tp.system_type_id = tp.user_type_id \
+             WHERE {} AND tp.name IN ('timestamp', 'rowversion')",
+            conditions.join(" AND ")
+        );
+        return execute::exec_direct(stmt, &sql);
+    }
 
+    // SQL_BEST_ROWID = 1: prefer an identity column; if the table has none,
+    // fall back to the primary key's columns; if there's no primary key
+    // either, fall back to the columns of its narrowest unique index.
     let sql = format!(
-        "SELECT CAST(2 AS SMALLINT) AS SCOPE, c.name AS COLUMN_NAME, \
-         CASE tp.name \
-           WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 \
-           WHEN 'tinyint' THEN -6 WHEN 'timestamp' THEN -2 WHEN 'rowversion' THEN -2 \
-           ELSE 12 END AS DATA_TYPE, \
-         tp.name AS TYPE_NAME, \
-         COALESCE(c.max_length, 0) AS COLUMN_SIZE, \
-         COALESCE(c.max_length, 0) AS BUFFER_LENGTH, \
-         c.scale AS DECIMAL_DIGITS, \
-         CAST(1 AS SMALLINT) AS PSEUDO_COLUMN \
-         FROM sys.columns c \
-         JOIN sys.tables t ON c.object_id = t.object_id \
-         JOIN sys.schemas s ON t.schema_id = s.schema_id \
-         JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND tp.system_type_id = tp.user_type_id \
-         WHERE {} {}",
-        conditions.join(" AND "),
-        extra_filter
+        "WITH rowid_cols AS ( \
+           SELECT c.name AS COLUMN_NAME, tp.name AS TYPE_NAME, c.max_length, c.scale, \
+             CAST(1 AS INT) AS key_seq \
+           FROM sys.columns c \
+           JOIN sys.tables t ON c.object_id = t.object_id \
+           JOIN sys.schemas s ON t.schema_id = s.schema_id \
+           JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND tp.system_type_id = tp.user_type_id \
+           WHERE c.is_identity = 1 AND {0} \
+           UNION ALL \
+           SELECT c.name, tp.name, c.max_length, c.scale, ic.key_ordinal \
+           FROM sys.indexes i \
+           JOIN sys.index_columns ic ON i.object_id = ic.object_id AND i.index_id = ic.index_id \
+           JOIN sys.columns c ON ic.object_id = c.object_id AND ic.column_id = c.column_id \
+           JOIN sys.tables t ON i.object_id = t.object_id \
+           JOIN sys.schemas s ON t.schema_id = s.schema_id \
+           JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND tp.system_type_id = tp.user_type_id \
+           WHERE i.is_primary_key = 1 AND {0} \
+             AND NOT EXISTS (SELECT 1 FROM sys.columns ci WHERE ci.object_id = t.object_id AND ci.is_identity = 1) \
+           UNION ALL \
+           SELECT c.name, tp.name, c.max_length, c.scale, ic.key_ordinal \
+           FROM sys.indexes i \
+           JOIN sys.index_columns ic ON i.object_id = ic.object_id AND i.index_id = ic.index_id \
+           JOIN sys.columns c ON ic.object_id = c.object_id AND ic.column_id = c.column_id \
+           JOIN sys.tables t ON i.object_id = t.object_id \
+           JOIN sys.schemas s ON t.schema_id = s.schema_id \
+           JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND tp.system_type_id = tp.user_type_id \
+           WHERE i.is_unique = 1 AND i.is_primary_key = 0 AND {0} \
+             AND NOT EXISTS (SELECT 1 FROM sys.columns ci WHERE ci.object_id = t.object_id AND ci.is_identity = 1) \
+             AND NOT EXISTS (SELECT 1 FROM sys.indexes ipk WHERE ipk.object_id = t.object_id AND ipk.is_primary_key = 1) \
+             AND i.index_id = ( \
+               SELECT TOP 1 i2.index_id FROM sys.indexes i2 \
+               JOIN sys.index_columns ic2 ON i2.object_id = ic2.object_id AND i2.index_id = ic2.index_id \
+               WHERE i2.object_id = t.object_id AND i2.is_unique = 1 AND i2.is_primary_key = 0 \
+               GROUP BY i2.index_id ORDER BY COUNT(*)) \
+         ) \
+         SELECT CAST(1 AS SMALLINT) AS SCOPE, COLUMN_NAME, \
+           CASE TYPE_NAME \
+             WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 \
+             WHEN 'tinyint' THEN -6 WHEN 'timestamp' THEN -2 WHEN 'rowversion' THEN -2 \
+             ELSE 12 END AS DATA_TYPE, \
+           TYPE_NAME, \
+           CASE \
+             WHEN TYPE_NAME IN ('nvarchar', 'nchar', 'ntext') THEN \
+               CASE WHEN max_length = -1 THEN 2147483647 ELSE max_length / 2 END \
+             WHEN max_length = -1 THEN 2147483647 \
+             ELSE COALESCE(max_length, 0) END AS COLUMN_SIZE, \
+           CASE WHEN max_length = -1 THEN 2147483647 ELSE COALESCE(max_length, 0) END AS BUFFER_LENGTH, \
+           scale AS DECIMAL_DIGITS, \
+           CAST(1 AS SMALLINT) AS PSEUDO_COLUMN \
+         FROM rowid_cols ORDER BY key_seq",
+        conditions.join(" AND ")
     );
     execute::exec_direct(stmt, &sql)
 }
@@ -188,16 +424,16 @@ pub fn foreign_keys(
 ) -> SQLRETURN {
     let mut conditions = vec!["1=1".to_string()];
     if !pk_table.is_empty() {
-        conditions.push(format!("pk_t.name = N'{}'", pk_table.replace('\'', "''")));
+        conditions.push(format!("pk_t.name = {}", quote_literal(pk_table)));
     }
     if !pk_schema.is_empty() {
-        conditions.push(format!("pk_s.name = N'{}'", pk_schema.replace('\'', "''")));
+        conditions.push(format!("pk_s.name = {}", quote_literal(pk_schema)));
     }
     if !fk_table.is_empty() {
-        conditions.push(format!("fk_t.name = N'{}'", fk_table.replace('\'', "''")));
+        conditions.push(format!("fk_t.name = {}", quote_literal(fk_table)));
     }
     if !fk_schema.is_empty() {
-        conditions.push(format!("fk_s.name = N'{}'", fk_schema.replace('\'', "''")));
+        conditions.push(format!("fk_s.name = {}", quote_literal(fk_schema)));
     }
 
     let sql = format!(
@@ -206,8 +442,10 @@ pub fn foreign_keys(
          DB_NAME() AS FKTABLE_CAT, fk_s.name AS FKTABLE_SCHEM, fk_t.name AS FKTABLE_NAME, \
          fk_c.name AS FKCOLUMN_NAME, \
          fkc.constraint_column_id AS KEY_SEQ, \
-         CAST(1 AS SMALLINT) AS UPDATE_RULE, \
-         CAST(1 AS SMALLINT) AS DELETE_RULE, \
+         CAST(CASE fk.update_referential_action \
+             WHEN 0 THEN 3 WHEN 1 THEN 0 WHEN 2 THEN 2 WHEN 3 THEN 4 ELSE 3 END AS SMALLINT) AS UPDATE_RULE, \
+         CAST(CASE fk.delete_referential_action \
+             WHEN 0 THEN 3 WHEN 1 THEN 0 WHEN 2 THEN 2 WHEN 3 THEN 4 ELSE 3 END AS SMALLINT) AS DELETE_RULE, \
          fk.name AS FK_NAME, \
          pk_i.name AS PK_NAME, \
          CAST(7 AS SMALLINT) AS DEFERRABILITY \
@@ -5,88 +5,342 @@ use tabby::{AuthMethod, Config, EncryptionLevel};
 use tokio::net::TcpStream;
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 
-pub fn parse_connection_string(conn_str: &str) -> (String, u16, String, String, String, bool) {
-    let mut host = "localhost".to_string();
-    let mut port: u16 = 1433;
-    let mut database = "master".to_string();
-    let mut uid = String::new();
-    let mut pwd = String::new();
-    let mut trust_cert = false;
-
-    for part in conn_str.split(';') {
-        let part = part.trim();
-        if part.is_empty() {
-            continue;
+/// `Encrypt` connection-string values, mapped onto `tabby::EncryptionLevel`.
+/// `Strict` additionally disables the `TrustServerCertificate`/CA-file escape
+/// hatches, since it implies full certificate validation.
+#[derive(Clone, Copy, PartialEq)]
+pub enum EncryptMode {
+    Off,
+    Required,
+    Strict,
+}
+
+/// `Authentication` connection-string values.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AuthMode {
+    SqlPassword,
+    Windows,
+    ActiveDirectoryPassword,
+}
+
+/// Everything parsed out of an ODBC connection string, before it's used to
+/// build a `tabby::Config`.
+pub struct ConnectionOptions {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub uid: String,
+    pub pwd: String,
+    pub trust_cert: bool,
+    pub trust_cert_ca: Option<String>,
+    pub encrypt: EncryptMode,
+    pub auth: AuthMode,
+    pub application_name: Option<String>,
+    pub packet_size: Option<u16>,
+    pub multi_subnet_failover: bool,
+}
+
+/// Split a `;`-separated ODBC connection string into `(key, value)` pairs,
+/// honoring `{...}` brace quoting (so values containing `;` parse correctly)
+/// where a doubled `}}` inside braces is a literal `}`.
+fn split_pairs(conn_str: &str) -> Vec<(String, String)> {
+    let chars: Vec<char> = conn_str.chars().collect();
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        while i < chars.len() && (chars[i] == ';' || chars[i].is_whitespace()) {
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
+        }
+        let key_start = i;
+        while i < chars.len() && chars[i] != '=' {
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
         }
-        if let Some(idx) = part.find('=') {
-            let key = part[..idx].trim().to_lowercase();
-            let val = part[idx + 1..].trim().to_string();
-            match key.as_str() {
-                "server" => {
-                    if let Some(comma) = val.find(',') {
-                        host = val[..comma].to_string();
-                        if let Ok(p) = val[comma + 1..].trim().parse() {
-                            port = p;
-                        }
-                    } else {
-                        host = val;
+        let key: String = chars[key_start..i].iter().collect::<String>().trim().to_string();
+        i += 1; // skip '='
+        while i < chars.len() && chars[i] != ';' && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let value = if i < chars.len() && chars[i] == '{' {
+            i += 1;
+            let mut value = String::new();
+            loop {
+                if i >= chars.len() {
+                    break;
+                }
+                if chars[i] == '}' {
+                    if i + 1 < chars.len() && chars[i + 1] == '}' {
+                        value.push('}');
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            while i < chars.len() && chars[i] != ';' {
+                i += 1;
+            }
+            value
+        } else {
+            let value_start = i;
+            while i < chars.len() && chars[i] != ';' {
+                i += 1;
+            }
+            chars[value_start..i].iter().collect::<String>().trim().to_string()
+        };
+
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+fn parse_bool(val: &str) -> bool {
+    val.eq_ignore_ascii_case("yes") || val == "1" || val.eq_ignore_ascii_case("true")
+}
+
+/// Masks the values of `UID`/`PWD` pairs (and their `User ID`/`Password`
+/// aliases, same as `parse_connection_string` accepts) in a connection
+/// string so it's safe to hand to `trace::emit`/a trace file without
+/// leaking credentials.
+pub fn redact_conn_str(conn_str: &str) -> String {
+    split_pairs(conn_str)
+        .into_iter()
+        .map(|(key, value)| {
+            if matches!(key.to_lowercase().as_str(), "uid" | "user id" | "pwd" | "password") {
+                format!("{}=***", key)
+            } else {
+                format!("{}={}", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+pub fn parse_connection_string(conn_str: &str) -> ConnectionOptions {
+    let mut opts = ConnectionOptions {
+        host: "localhost".to_string(),
+        port: 1433,
+        database: "master".to_string(),
+        uid: String::new(),
+        pwd: String::new(),
+        trust_cert: false,
+        trust_cert_ca: None,
+        encrypt: EncryptMode::Required,
+        auth: AuthMode::SqlPassword,
+        application_name: None,
+        packet_size: None,
+        multi_subnet_failover: false,
+    };
+
+    for (key, val) in split_pairs(conn_str) {
+        match key.to_lowercase().as_str() {
+            "server" => {
+                if let Some(comma) = val.find(',') {
+                    opts.host = val[..comma].to_string();
+                    if let Ok(p) = val[comma + 1..].trim().parse() {
+                        opts.port = p;
                     }
+                } else {
+                    opts.host = val;
+                }
+            }
+            "database" | "initial catalog" => opts.database = val,
+            "uid" | "user id" => opts.uid = val,
+            "pwd" | "password" => opts.pwd = val,
+            "trustservercertificate" => opts.trust_cert = parse_bool(&val),
+            "trustservercertificateca" | "servercertificate" => opts.trust_cert_ca = Some(val),
+            "encrypt" => {
+                opts.encrypt = match val.to_lowercase().as_str() {
+                    "no" | "optional" | "0" | "false" => EncryptMode::Off,
+                    "strict" => EncryptMode::Strict,
+                    _ => EncryptMode::Required, // "yes" / "mandatory" / unknown defaults safe
+                }
+            }
+            "authentication" => {
+                opts.auth = match val.to_lowercase().as_str() {
+                    "windows" | "integrated" => AuthMode::Windows,
+                    "activedirectorypassword" => AuthMode::ActiveDirectoryPassword,
+                    _ => AuthMode::SqlPassword,
                 }
-                "database" | "initial catalog" => database = val,
-                "uid" | "user id" => uid = val,
-                "pwd" | "password" => pwd = val,
-                "trustservercertificate" => {
-                    trust_cert = val.eq_ignore_ascii_case("yes")
-                        || val == "1"
-                        || val.eq_ignore_ascii_case("true")
+            }
+            "application name" | "app" => opts.application_name = Some(val),
+            "packet size" => {
+                if let Ok(p) = val.parse() {
+                    opts.packet_size = Some(p);
                 }
-                _ => {}
             }
+            "multisubnetfailover" => opts.multi_subnet_failover = parse_bool(&val),
+            _ => {}
         }
     }
-    (host, port, database, uid, pwd, trust_cert)
+    opts
+}
+
+/// Resolve `host:port` and connect, trying every address DNS returns in turn
+/// when `multi_subnet_failover` is set, so a downed replica in a
+/// multi-subnet availability group doesn't stall the whole connect attempt
+/// on the first (possibly unreachable) address.
+async fn connect_tcp(host: &str, port: u16, multi_subnet_failover: bool) -> Result<TcpStream, String> {
+    if !multi_subnet_failover {
+        return TcpStream::connect((host, port)).await.map_err(|e| e.to_string());
+    }
+
+    let addrs: Vec<std::net::SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| e.to_string())?
+        .collect();
+    let mut last_err = format!("could not resolve {}:{}", host, port);
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_err = e.to_string(),
+        }
+    }
+    Err(last_err)
 }
 
 pub fn driver_connect(conn: &mut Connection, conn_str: &str) -> SQLRETURN {
-    let (host, port, database, uid, pwd, trust_cert) = parse_connection_string(conn_str);
-    conn.server = format!("{}:{}", host, port);
-    conn.database = database.clone();
-    conn.uid = uid.clone();
-    conn.pwd = pwd.clone();
+    let opts = parse_connection_string(conn_str);
+    conn.server = format!("{}:{}", opts.host, opts.port);
+    conn.database = opts.database.clone();
+    conn.uid = opts.uid.clone();
+    conn.pwd = opts.pwd.clone();
+
+    // SQL_ATTR_CONNECTION_TIMEOUT bounds just the TCP connect, while
+    // SQL_ATTR_LOGIN_TIMEOUT bounds the whole login sequence (connect + TDS
+    // handshake); a zero value means "no timeout" per the ODBC spec.
+    let connection_timeout_secs = conn.connection_timeout_secs;
+    let login_timeout_secs = conn.login_timeout_secs;
+
+    enum TimeoutKind {
+        Connection,
+        Login,
+    }
+    let timed_out: std::cell::Cell<Option<TimeoutKind>> = std::cell::Cell::new(None);
 
     let result = runtime::block_on(async {
-        let mut config = Config::new();
-        config.host(&host);
-        config.port(port);
-        config.database(&database);
-        config.authentication(AuthMethod::sql_server(&uid, &pwd));
-        if trust_cert {
-            config.trust_cert();
-        }
-        config.encryption(EncryptionLevel::Required);
+        let login = async {
+            let mut config = Config::new();
+            config.host(&opts.host);
+            config.port(opts.port);
+            config.database(&opts.database);
+            config.authentication(match opts.auth {
+                AuthMode::Windows => AuthMethod::windows(&opts.uid, &opts.pwd),
+                AuthMode::ActiveDirectoryPassword => AuthMethod::aad_password(&opts.uid, &opts.pwd),
+                AuthMode::SqlPassword => AuthMethod::sql_server(&opts.uid, &opts.pwd),
+            });
+            // Strict encryption implies full certificate validation, so the
+            // trust-anything/custom-CA escape hatches don't apply to it.
+            if opts.encrypt != EncryptMode::Strict {
+                if let Some(ca_path) = &opts.trust_cert_ca {
+                    config.trust_cert_ca(ca_path);
+                } else if opts.trust_cert {
+                    config.trust_cert();
+                }
+            }
+            config.encryption(match opts.encrypt {
+                EncryptMode::Off => EncryptionLevel::Off,
+                EncryptMode::Required | EncryptMode::Strict => EncryptionLevel::Required,
+            });
+            if let Some(name) = &opts.application_name {
+                config.application_name(name);
+            }
+            if let Some(size) = opts.packet_size {
+                config.packet_size(size);
+            }
+
+            let connect = connect_tcp(&opts.host, opts.port, opts.multi_subnet_failover);
+            let tcp = if connection_timeout_secs == 0 {
+                connect.await?
+            } else {
+                let duration = std::time::Duration::from_secs(connection_timeout_secs as u64);
+                match tokio::time::timeout(duration, connect).await {
+                    Ok(r) => r?,
+                    Err(_) => {
+                        timed_out.set(Some(TimeoutKind::Connection));
+                        return Err("connection timeout expired".to_string());
+                    }
+                }
+            };
+            tcp.set_nodelay(true).map_err(|e| e.to_string())?;
 
-        let tcp = TcpStream::connect(config.get_addr())
-            .await
-            .map_err(|e| e.to_string())?;
-        tcp.set_nodelay(true).map_err(|e| e.to_string())?;
+            let client = tabby::Client::connect(config, tcp.compat_write())
+                .await
+                .map_err(|e| e.to_string())?;
 
-        let client = tabby::Client::connect(config, tcp.compat_write())
-            .await
-            .map_err(|e| e.to_string())?;
+            Ok::<_, String>(client)
+        };
 
-        Ok::<_, String>(client)
+        if login_timeout_secs == 0 {
+            login.await
+        } else {
+            let duration = std::time::Duration::from_secs(login_timeout_secs as u64);
+            match tokio::time::timeout(duration, login).await {
+                Ok(r) => r,
+                Err(_) => {
+                    timed_out.set(Some(TimeoutKind::Login));
+                    Err("login timeout expired".to_string())
+                }
+            }
+        }
     });
 
+    match timed_out.into_inner() {
+        Some(TimeoutKind::Connection) => {
+            conn.diagnostics.push(DiagRecord {
+                state: "HYT01".to_string(),
+                native_error: 0,
+                message: "Connection timeout expired".to_string(),
+            });
+            return SQL_ERROR;
+        }
+        Some(TimeoutKind::Login) => {
+            conn.diagnostics.push(DiagRecord {
+                state: "HYT00".to_string(),
+                native_error: 0,
+                message: "Login timeout expired".to_string(),
+            });
+            return SQL_ERROR;
+        }
+        None => {}
+    }
+
     match result {
         Ok(client) => {
             conn.client = Some(client);
             conn.connected = true;
+            let redacted = redact_conn_str(conn_str);
+            let event = TraceEvent::Connect {
+                server: &conn.server,
+                database: &conn.database,
+                conn_str: &redacted,
+            };
+            if let Some(cb) = conn.trace_callback.as_ref() {
+                cb(&event);
+            }
+            crate::trace::emit(&event);
             SQL_SUCCESS
         }
         Err(msg) => {
+            let native = crate::execute::extract_error_number(&msg);
+            let state = if native != 0 {
+                crate::execute::sqlstate_for_native_error(native, crate::execute::extract_severity(&msg))
+                    .to_string()
+            } else {
+                "08001".to_string()
+            };
             conn.diagnostics.push(DiagRecord {
-                state: "08001".to_string(),
-                native_error: 0,
+                state,
+                native_error: native,
                 message: msg,
             });
             SQL_ERROR
@@ -95,6 +349,14 @@ pub fn driver_connect(conn: &mut Connection, conn_str: &str) -> SQLRETURN {
 }
 
 pub fn disconnect(conn: &mut Connection) -> SQLRETURN {
+    let event = TraceEvent::Disconnect {
+        server: &conn.server,
+        database: &conn.database,
+    };
+    if let Some(cb) = conn.trace_callback.as_ref() {
+        cb(&event);
+    }
+    crate::trace::emit(&event);
     conn.client = None;
     conn.connected = false;
     SQL_SUCCESS
@@ -2,6 +2,48 @@ use crate::handle::*;
 use crate::types::*;
 use std::ptr;
 
+/// Transcodes `msg` from its internal UTF-8 representation into `charset`
+/// (an `encoding_rs` label; unrecognized labels fall back to UTF-8),
+/// truncating to at most `max_bytes` without splitting a multibyte
+/// character. Returns the (possibly truncated) encoded bytes alongside the
+/// byte length the *full*, untruncated message would occupy in this
+/// encoding, for `text_length`.
+fn encode_diag_message(msg: &str, charset: &str, max_bytes: usize) -> (Vec<u8>, usize) {
+    let encoding = encoding_rs::Encoding::for_label(charset.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+    let (full, _, _) = encoding.encode(msg);
+    if full.len() <= max_bytes {
+        return (full.into_owned(), full.len());
+    }
+    // `encoding_rs`'s Encoder is stateful, but diagnostic messages are short
+    // enough that re-encoding each char in turn and stopping once the next
+    // one would overflow the budget is a cheap way to guarantee we only ever
+    // cut at a character boundary.
+    let mut truncated = Vec::new();
+    for ch in msg.chars() {
+        let mut buf = [0u8; 4];
+        let (enc, _, _) = encoding.encode(ch.encode_utf8(&mut buf));
+        if truncated.len() + enc.len() > max_bytes {
+            break;
+        }
+        truncated.extend_from_slice(&enc);
+    }
+    (truncated, full.len())
+}
+
+/// Resolves the charset a handle's diagnostics should be transcoded to —
+/// `SQL_ATTR_FURBALL_CHARSET` on the owning connection, or plain UTF-8 for
+/// handle types (env) that have no such attribute.
+fn diag_charset(handle_type: SQLSMALLINT, handle: SQLHANDLE) -> String {
+    match handle_type {
+        SQL_HANDLE_DBC => unsafe { &*(handle as *const Connection) }.charset.clone(),
+        SQL_HANDLE_STMT => {
+            let stmt = unsafe { &*(handle as *const Statement) };
+            unsafe { &*stmt.conn }.charset.clone()
+        }
+        _ => "UTF-8".to_string(),
+    }
+}
+
 pub fn get_diag_rec(
     handle_type: SQLSMALLINT,
     handle: SQLHANDLE,
@@ -15,9 +57,15 @@ pub fn get_diag_rec(
     if handle.is_null() {
         return SQL_INVALID_HANDLE;
     }
+    if buffer_length < 0 {
+        return SQL_ERROR;
+    }
 
     let diagnostics: &[DiagRecord] = match handle_type {
-        SQL_HANDLE_ENV => return SQL_NO_DATA, // env has no diagnostics in our impl
+        SQL_HANDLE_ENV => {
+            let env = unsafe { &*(handle as *const Environment) };
+            &env.diagnostics
+        }
         SQL_HANDLE_DBC => {
             let conn = unsafe { &*(handle as *const Connection) };
             &conn.diagnostics
@@ -55,20 +103,329 @@ pub fn get_diag_rec(
         }
     }
 
-    let msg_bytes = rec.message.as_bytes();
+    let charset = diag_charset(handle_type, handle);
+    let max_bytes = if buffer_length > 0 {
+        (buffer_length as usize) - 1
+    } else {
+        0
+    };
+    let (msg_bytes, full_len) = encode_diag_message(&rec.message, &charset, max_bytes);
+    if !text_length.is_null() {
+        unsafe {
+            *text_length = full_len as SQLSMALLINT;
+        }
+    }
+
+    if !message_text.is_null() && buffer_length > 0 {
+        unsafe {
+            ptr::copy_nonoverlapping(msg_bytes.as_ptr(), message_text, msg_bytes.len());
+            *message_text.add(msg_bytes.len()) = 0;
+        }
+    }
+
+    if msg_bytes.len() < full_len {
+        SQL_SUCCESS_WITH_INFO
+    } else {
+        SQL_SUCCESS
+    }
+}
+
+/// Mirrors `get_diag_rec`, but writes `SQLWCHAR` (UTF-16) output for Unicode
+/// callers (the Windows Driver Manager and any app linking the `W` entry
+/// points) instead of transcoding through `SQL_ATTR_FURBALL_CHARSET` — wide
+/// callers always get UTF-16 regardless of that attribute.
+pub fn get_diag_rec_w(
+    handle_type: SQLSMALLINT,
+    handle: SQLHANDLE,
+    rec_number: SQLSMALLINT,
+    sql_state: *mut SQLWCHAR,
+    native_error: *mut SQLINTEGER,
+    message_text: *mut SQLWCHAR,
+    buffer_length: SQLSMALLINT,
+    text_length: *mut SQLSMALLINT,
+) -> SQLRETURN {
+    if handle.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    if buffer_length < 0 {
+        return SQL_ERROR;
+    }
+
+    let diagnostics: &[DiagRecord] = match handle_type {
+        SQL_HANDLE_ENV => {
+            let env = unsafe { &*(handle as *const Environment) };
+            &env.diagnostics
+        }
+        SQL_HANDLE_DBC => {
+            let conn = unsafe { &*(handle as *const Connection) };
+            &conn.diagnostics
+        }
+        SQL_HANDLE_STMT => {
+            let stmt = unsafe { &*(handle as *const Statement) };
+            &stmt.diagnostics
+        }
+        _ => return SQL_INVALID_HANDLE,
+    };
+
+    let idx = (rec_number as usize).wrapping_sub(1);
+    if idx >= diagnostics.len() {
+        return SQL_NO_DATA;
+    }
+    let rec = &diagnostics[idx];
+
+    if !sql_state.is_null() {
+        let state_utf16: Vec<u16> = rec.state.encode_utf16().collect();
+        let copy_len = std::cmp::min(state_utf16.len(), 5);
+        unsafe {
+            ptr::copy_nonoverlapping(state_utf16.as_ptr(), sql_state, copy_len);
+            for i in copy_len..6 {
+                *sql_state.add(i) = 0;
+            }
+        }
+    }
+
+    if !native_error.is_null() {
+        unsafe {
+            *native_error = rec.native_error;
+        }
+    }
+
+    let msg_utf16: Vec<u16> = rec.message.encode_utf16().collect();
+    let full_len = msg_utf16.len();
+    let max_units = if buffer_length > 0 {
+        (buffer_length as usize) - 1
+    } else {
+        0
+    };
+    let mut copy_len = std::cmp::min(msg_utf16.len(), max_units);
+    if copy_len < msg_utf16.len() && copy_len > 0 && (0xD800..=0xDBFF).contains(&msg_utf16[copy_len - 1]) {
+        // Don't strand a lone leading surrogate at the truncation boundary.
+        copy_len -= 1;
+    }
+
     if !text_length.is_null() {
         unsafe {
-            *text_length = msg_bytes.len() as SQLSMALLINT;
+            *text_length = full_len as SQLSMALLINT;
         }
     }
 
     if !message_text.is_null() && buffer_length > 0 {
-        let copy_len = std::cmp::min(msg_bytes.len(), (buffer_length as usize).saturating_sub(1));
         unsafe {
-            ptr::copy_nonoverlapping(msg_bytes.as_ptr(), message_text, copy_len);
+            ptr::copy_nonoverlapping(msg_utf16.as_ptr(), message_text, copy_len);
             *message_text.add(copy_len) = 0;
         }
     }
 
+    if copy_len < full_len {
+        SQL_SUCCESS_WITH_INFO
+    } else {
+        SQL_SUCCESS
+    }
+}
+
+/// Write a string into `diag_info`, honoring `buffer_length` and reporting
+/// the untruncated length through `string_length` — the same truncation
+/// contract `get_diag_rec` uses for SQLSTATE/message text.
+unsafe fn write_diag_string(
+    s: &str,
+    diag_info: SQLPOINTER,
+    buffer_length: SQLSMALLINT,
+    string_length: *mut SQLSMALLINT,
+) -> SQLRETURN {
+    let bytes = s.as_bytes();
+    if !string_length.is_null() {
+        unsafe {
+            *string_length = bytes.len() as SQLSMALLINT;
+        }
+    }
+    if !diag_info.is_null() && buffer_length > 0 {
+        let dest = diag_info as *mut SQLCHAR;
+        let copy_len = std::cmp::min(bytes.len(), (buffer_length as usize).saturating_sub(1));
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), dest, copy_len);
+            *dest.add(copy_len) = 0;
+        }
+        if bytes.len() >= buffer_length as usize {
+            return SQL_SUCCESS_WITH_INFO;
+        }
+    }
     SQL_SUCCESS
 }
+
+/// SQLSTATEs ODBC defines itself (third character `S`, e.g. `01S02`,
+/// `08S01`) or that come from the driver manager (`IM...`) report
+/// "ODBC 3.0" as their class/subclass origin; everything else traces back
+/// to the ISO/ANSI SQL standard.
+fn diag_origin(state: &str) -> &'static str {
+    let bytes = state.as_bytes();
+    if state.starts_with("IM") || bytes.get(2) == Some(&b'S') {
+        "ODBC 3.0"
+    } else {
+        "ISO 9075"
+    }
+}
+
+pub fn get_diag_field(
+    handle_type: SQLSMALLINT,
+    handle: SQLHANDLE,
+    rec_number: SQLSMALLINT,
+    diag_identifier: SQLSMALLINT,
+    diag_info: SQLPOINTER,
+    buffer_length: SQLSMALLINT,
+    string_length: *mut SQLSMALLINT,
+) -> SQLRETURN {
+    if handle.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+
+    let (diagnostics, row_count): (&[DiagRecord], SQLLEN) = match handle_type {
+        SQL_HANDLE_ENV => {
+            let env = unsafe { &*(handle as *const Environment) };
+            (&env.diagnostics, -1)
+        }
+        SQL_HANDLE_DBC => {
+            let conn = unsafe { &*(handle as *const Connection) };
+            (&conn.diagnostics, -1)
+        }
+        SQL_HANDLE_STMT => {
+            let stmt = unsafe { &*(handle as *const Statement) };
+            (&stmt.diagnostics, stmt.row_count)
+        }
+        _ => return SQL_INVALID_HANDLE,
+    };
+
+    // Header fields apply to the handle as a whole (rec_number == 0).
+    if rec_number == 0 {
+        return match diag_identifier {
+            SQL_DIAG_NUMBER => {
+                if !diag_info.is_null() {
+                    unsafe {
+                        *(diag_info as *mut SQLINTEGER) = diagnostics.len() as SQLINTEGER;
+                    }
+                }
+                SQL_SUCCESS
+            }
+            SQL_DIAG_ROW_COUNT | SQL_DIAG_CURSOR_ROW_COUNT => {
+                if !diag_info.is_null() {
+                    unsafe {
+                        *(diag_info as *mut SQLLEN) = row_count;
+                    }
+                }
+                SQL_SUCCESS
+            }
+            SQL_DIAG_RETURNCODE => {
+                if !diag_info.is_null() {
+                    unsafe {
+                        *(diag_info as *mut SQLRETURN) = SQL_SUCCESS;
+                    }
+                }
+                SQL_SUCCESS
+            }
+            _ => SQL_ERROR,
+        };
+    }
+
+    let idx = (rec_number as usize).wrapping_sub(1);
+    if idx >= diagnostics.len() {
+        return SQL_NO_DATA;
+    }
+    let rec = &diagnostics[idx];
+
+    match diag_identifier {
+        SQL_DIAG_SQLSTATE => unsafe {
+            write_diag_string(&rec.state, diag_info, buffer_length, string_length)
+        },
+        SQL_DIAG_NATIVE => {
+            if !diag_info.is_null() {
+                unsafe {
+                    *(diag_info as *mut SQLINTEGER) = rec.native_error;
+                }
+            }
+            SQL_SUCCESS
+        }
+        SQL_DIAG_MESSAGE_TEXT => unsafe {
+            write_diag_string(&rec.message, diag_info, buffer_length, string_length)
+        },
+        SQL_DIAG_CLASS_ORIGIN | SQL_DIAG_SUBCLASS_ORIGIN => unsafe {
+            write_diag_string(diag_origin(&rec.state), diag_info, buffer_length, string_length)
+        },
+        _ => SQL_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_with_diag(message: &str) -> Environment {
+        Environment {
+            odbc_version: SQL_OV_ODBC3,
+            connections: Vec::new(),
+            diagnostics: vec![DiagRecord {
+                state: "01004".to_string(),
+                native_error: 0,
+                message: message.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn get_diag_rec_truncates_and_reports_full_length() {
+        let mut env = env_with_diag("a long diagnostic message");
+        let handle = &mut env as *mut Environment as SQLHANDLE;
+        let mut message_text = [0u8; 8]; // room for 7 chars + NUL
+        let mut text_length: SQLSMALLINT = 0;
+        let ret = get_diag_rec(
+            SQL_HANDLE_ENV,
+            handle,
+            1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            message_text.as_mut_ptr(),
+            message_text.len() as SQLSMALLINT,
+            &mut text_length,
+        );
+        assert_eq!(ret, SQL_SUCCESS_WITH_INFO);
+        assert_eq!(text_length as usize, "a long diagnostic message".len());
+        assert_eq!(&message_text[..7], b"a long ");
+        assert_eq!(message_text[7], 0);
+    }
+
+    #[test]
+    fn get_diag_rec_fits_without_truncation() {
+        let mut env = env_with_diag("ok");
+        let handle = &mut env as *mut Environment as SQLHANDLE;
+        let mut message_text = [0u8; 16];
+        let mut text_length: SQLSMALLINT = 0;
+        let ret = get_diag_rec(
+            SQL_HANDLE_ENV,
+            handle,
+            1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            message_text.as_mut_ptr(),
+            message_text.len() as SQLSMALLINT,
+            &mut text_length,
+        );
+        assert_eq!(ret, SQL_SUCCESS);
+        assert_eq!(text_length, 2);
+    }
+
+    #[test]
+    fn get_diag_rec_rejects_negative_buffer_length() {
+        let mut env = env_with_diag("short");
+        let handle = &mut env as *mut Environment as SQLHANDLE;
+        let mut text_length: SQLSMALLINT = 0;
+        let ret = get_diag_rec(
+            SQL_HANDLE_ENV,
+            handle,
+            1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            -1,
+            &mut text_length,
+        );
+        assert_eq!(ret, SQL_ERROR);
+    }
+}
@@ -25,10 +25,23 @@ pub fn exec_direct(stmt: &mut Statement, sql: &str) -> SQLRETURN {
 
     // If autocommit is OFF and we're not already in a transaction, start one
     if !conn.autocommit && !conn.in_transaction {
+        let isolation_sql = match conn.isolation_level {
+            SQL_TXN_READ_UNCOMMITTED => "READ UNCOMMITTED",
+            SQL_TXN_REPEATABLE_READ => "REPEATABLE READ",
+            SQL_TXN_SERIALIZABLE => "SERIALIZABLE",
+            SQL_TXN_SS_SNAPSHOT => "SNAPSHOT",
+            _ => "READ COMMITTED",
+        };
         let begin_result = runtime::block_on(async {
             let mut w = StringRowWriter::new();
             client
-                .batch_into("BEGIN TRANSACTION", &mut w)
+                .batch_into(
+                    format!(
+                        "SET TRANSACTION ISOLATION LEVEL {}; BEGIN TRANSACTION",
+                        isolation_sql
+                    ),
+                    &mut w,
+                )
                 .await
                 .map_err(|e| e.to_string())
         });
@@ -41,18 +54,142 @@ pub fn exec_direct(stmt: &mut Statement, sql: &str) -> SQLRETURN {
             return SQL_ERROR;
         }
         conn.in_transaction = true;
+        let event = TraceEvent::Transaction {
+            server: &conn.server,
+            database: &conn.database,
+            kind: "BEGIN",
+        };
+        if let Some(cb) = conn.trace_callback.as_ref() {
+            cb(&event);
+        }
+        crate::trace::emit(&event);
     }
 
     let sql = sql.to_string();
 
+    if stmt.cursor_type != SQL_CURSOR_FORWARD_ONLY {
+        // Scrollable cursor requested — SQLFetchScroll needs random access, so
+        // buffer the whole result set in memory instead of streaming it.
+        let trace_start = std::time::Instant::now();
+        let ret = exec_buffered(stmt, client, &sql);
+        let outcome = if ret == SQL_SUCCESS || ret == SQL_SUCCESS_WITH_INFO {
+            Ok(stmt.row_count)
+        } else {
+            Err(stmt
+                .diagnostics
+                .last()
+                .map(|d| d.message.as_str())
+                .unwrap_or(""))
+        };
+        let event = TraceEvent::Query {
+            server: &conn.server,
+            database: &conn.database,
+            stmt_id: stmt.trace_id,
+            sql: &sql,
+            elapsed: trace_start.elapsed(),
+            result: outcome,
+        };
+        if let Some(cb) = conn.trace_callback.as_ref() {
+            cb(&event);
+        }
+        crate::trace::emit(&event);
+        return ret;
+    }
+
     // Use streaming API: send query, read only until metadata
     let mut rows_affected = 0u64;
+    let query_timeout_secs = stmt.query_timeout_secs;
+    // Fresh token for this execution, so a cancel delivered for a previous,
+    // already-finished statement can't leak into this one.
+    stmt.cancel_token = std::sync::Arc::new(tokio::sync::Notify::new());
+    let cancel_token = stmt.cancel_token.clone();
+    // SQL_ATTR_QUERY_TIMEOUT == 0 means "no timeout" per the ODBC spec.
+    let timed_out = std::cell::Cell::new(false);
+    let cancelled = std::cell::Cell::new(false);
+    let trace_start = std::time::Instant::now();
     let result = runtime::block_on(async {
-        client
-            .batch_start_with_rowcount(sql, &mut rows_affected)
-            .await
-            .map_err(|e| e.to_string())
+        let raced = async {
+            tokio::select! {
+                r = client.batch_start_with_rowcount(sql.clone(), &mut rows_affected) => {
+                    Some(r.map_err(|e| e.to_string()))
+                }
+                _ = cancel_token.notified() => None,
+            }
+        };
+
+        let outcome = if query_timeout_secs == 0 {
+            raced.await
+        } else {
+            let duration = std::time::Duration::from_secs(query_timeout_secs as u64);
+            match tokio::time::timeout(duration, raced).await {
+                Ok(o) => o,
+                Err(_) => {
+                    timed_out.set(true);
+                    return Err("query timeout expired".to_string());
+                }
+            }
+        };
+
+        match outcome {
+            Some(r) => r,
+            None => {
+                cancelled.set(true);
+                // Flush a TDS attention signal and drain its ack so the
+                // connection comes back to a usable state before we report
+                // the cancel to the caller.
+                let _ = client.send_attention().await;
+                let _ = client.batch_drain().await;
+                Err("query cancelled".to_string())
+            }
+        }
     });
+    let elapsed = trace_start.elapsed();
+
+    {
+        let outcome: Result<SQLLEN, &str> = if timed_out.get() {
+            Err("Query timeout expired")
+        } else if cancelled.get() {
+            Err("Operation cancelled")
+        } else {
+            match &result {
+                Ok(columns) if columns.is_empty() => Ok(rows_affected as SQLLEN),
+                Ok(_) => Ok(-1), // result set — row count isn't known up front
+                Err(msg) => Err(msg.as_str()),
+            }
+        };
+        let event = TraceEvent::Query {
+            server: &conn.server,
+            database: &conn.database,
+            stmt_id: stmt.trace_id,
+            sql: &sql,
+            elapsed,
+            result: outcome,
+        };
+        if let Some(cb) = conn.trace_callback.as_ref() {
+            cb(&event);
+        }
+        crate::trace::emit(&event);
+    }
+
+    if timed_out.get() {
+        stmt.diagnostics.push(DiagRecord {
+            state: "HYT00".to_string(),
+            native_error: 0,
+            message: "Query timeout expired".to_string(),
+        });
+        return SQL_ERROR;
+    }
+
+    if cancelled.get() {
+        stmt.diagnostics.push(DiagRecord {
+            state: "HY008".to_string(),
+            native_error: 0,
+            message: "Operation cancelled".to_string(),
+        });
+        stmt.streaming = false;
+        stmt.read_offsets.clear();
+        return SQL_ERROR;
+    }
 
     match result {
         Ok(columns) => {
@@ -71,18 +208,26 @@ pub fn exec_direct(stmt: &mut Statement, sql: &str) -> SQLRETURN {
                 stmt.read_offsets.clear();
                 stmt.pending_result_sets.clear();
                 stmt.current_row.clear();
+                stmt.rows_fetched = 0;
             } else {
                 // Has result set — set up columns, enable streaming
                 stmt.columns = columns
                     .iter()
                     .map(|c| {
-                        let (sql_type, size, decimal_digits, nullable) = sql_type_from_column(c);
+                        let (sql_type, size, decimal_digits, nullable, ss_type) =
+                            sql_type_from_column(c);
+                        let (base_table, base_column, schema, catalog) = column_provenance(c);
                         ColumnDesc {
                             name: c.name().to_string(),
                             sql_type,
                             size,
                             decimal_digits,
                             nullable,
+                            ss_type,
+                            base_table,
+                            base_column,
+                            schema,
+                            catalog,
                         }
                     })
                     .collect();
@@ -94,6 +239,7 @@ pub fn exec_direct(stmt: &mut Statement, sql: &str) -> SQLRETURN {
                 stmt.read_offsets.clear();
                 stmt.pending_result_sets.clear();
                 stmt.current_row.clear();
+                stmt.rows_fetched = 0;
             }
             SQL_SUCCESS
         }
@@ -109,19 +255,96 @@ pub fn exec_direct(stmt: &mut Statement, sql: &str) -> SQLRETURN {
     }
 }
 
-/// Parse SQL Server error number from error message and map to SQLSTATE
+/// Execute `sql` and buffer every result set in memory up front, for
+/// statements bound to a scrollable cursor type where `SQLFetchScroll` needs
+/// random access instead of the forward-only streaming `exec_direct` uses by
+/// default.
+fn exec_buffered(
+    stmt: &mut Statement,
+    client: &mut tabby::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>,
+    sql: &str,
+) -> SQLRETURN {
+    let mut w = StringRowWriter::new();
+    let result = runtime::block_on(async { client.batch_into(sql, &mut w).await.map_err(|e| e.to_string()) });
+    w.finalize();
+
+    match result {
+        Ok(_) => {
+            for (number, message) in w.info_messages {
+                stmt.diagnostics.push(DiagRecord {
+                    state: "01000".to_string(),
+                    native_error: number as i32,
+                    message,
+                });
+            }
+            let mut result_sets = w.result_sets;
+            if result_sets.is_empty() {
+                stmt.columns = Vec::new();
+                stmt.rows = Vec::new();
+                stmt.row_count = -1;
+            } else {
+                let mut first = result_sets.remove(0);
+                let rows = first.into_rows();
+                stmt.row_count = rows.len() as SQLLEN;
+                stmt.columns = first.columns;
+                stmt.rows = rows;
+            }
+            stmt.pending_result_sets = result_sets;
+            stmt.row_index = -1;
+            stmt.executed = true;
+            stmt.streaming = false;
+            stmt.read_offsets.clear();
+            stmt.current_row.clear();
+            stmt.rows_fetched = 0;
+            SQL_SUCCESS
+        }
+        Err(msg) => {
+            let (state, native) = map_sqlstate(&msg);
+            stmt.diagnostics.push(DiagRecord {
+                state,
+                native_error: native,
+                message: msg,
+            });
+            SQL_ERROR
+        }
+    }
+}
+
+/// Map a SQL Server message number to the closest five-character ODBC
+/// SQLSTATE, modeled on the classification tables client libraries build for
+/// `sys.messages`. Numbers outside this table fall back to the message's
+/// severity/class: anything above the user-error band (severity > 16)
+/// degrades to the catch-all "HY000", while purely informational severities
+/// are reported as a warning instead of a hard error.
+pub(crate) fn sqlstate_for_native_error(native: i32, severity: Option<u8>) -> &'static str {
+    match native {
+        2627 | 2601 | 547 => "23000", // constraint violation / duplicate key
+        208 => "42S02",               // invalid object name
+        2812 => "42000",              // could not find stored procedure
+        102 | 105 | 156 | 170 | 229 | 230 => "42000", // syntax error / permission denied
+        18456 => "28000",            // login failed
+        4060 => "08004",             // cannot open database requested by login
+        10054 | -2 => "08S01",       // communication link failure / timeout
+        1205 => "40001",             // deadlock victim
+        8152 => "22001",             // string or binary data would be truncated
+        8114 | 245 => "22018",       // invalid character value for cast
+        8115 => "22003",             // arithmetic overflow
+        _ => match severity {
+            Some(s) if s <= 10 => "01000",
+            _ => "HY000",
+        },
+    }
+}
+
+/// Parse SQL Server error number and severity from an error message and map
+/// the number to SQLSTATE.
 fn map_sqlstate(msg: &str) -> (String, i32) {
     let native = extract_error_number(msg);
-    let state = match native {
-        2627 | 2601 | 547 => "23000",
-        208 => "42S02",
-        156 | 102 => "42000",
-        _ => "HY000",
-    };
-    (state.to_string(), native)
+    let severity = extract_severity(msg);
+    (sqlstate_for_native_error(native, severity).to_string(), native)
 }
 
-fn extract_error_number(msg: &str) -> i32 {
+pub(crate) fn extract_error_number(msg: &str) -> i32 {
     if let Some(idx) = msg.find("code: ") {
         let rest = &msg[idx + 6..];
         if let Some(end) = rest.find(|c: char| !c.is_ascii_digit()) {
@@ -152,3 +375,15 @@ fn extract_error_number(msg: &str) -> i32 {
     }
     0
 }
+
+/// Best-effort extraction of the SQL Server severity/class from an error
+/// message (e.g. "...Severity: 16..."), used to classify numbers that aren't
+/// in the explicit `sqlstate_for_native_error` table.
+pub(crate) fn extract_severity(msg: &str) -> Option<u8> {
+    let idx = msg.find("Severity: ").or_else(|| msg.find("severity "))?;
+    let rest = &msg[idx..];
+    let digit_start = rest.find(|c: char| c.is_ascii_digit())?;
+    let rest = &rest[digit_start..];
+    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
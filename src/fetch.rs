@@ -76,6 +76,111 @@ impl<'a> tabby::RowWriter for SingleRowWriter<'a> {
 }
 
 pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
+    if stmt.bound_cols.is_empty() {
+        fetch_one_row(stmt)
+    } else {
+        fetch_block(stmt)
+    }
+}
+
+/// Drain up to `row_array_size` rows via `fetch_one_row`, writing each bound
+/// column's value into its bound buffer at the right row stride (column-wise
+/// when `row_bind_type == SQL_BIND_BY_COLUMN`, or row-wise at a
+/// `row_bind_type`-byte struct stride otherwise) by reusing `get_data`'s
+/// per-type conversion logic against a pointer computed for that row.
+fn fetch_block(stmt: &mut Statement) -> SQLRETURN {
+    let rowset_size = stmt.row_array_size.max(1) as usize;
+    let mut statuses = Vec::with_capacity(rowset_size);
+    let mut any_info = false;
+    let mut any_error = false;
+
+    for row_idx in 0..rowset_size {
+        match fetch_one_row(stmt) {
+            SQL_NO_DATA => break,
+            SQL_ERROR => {
+                statuses.push(SQL_ROW_ERROR);
+                any_error = true;
+                break;
+            }
+            ret => {
+                if ret == SQL_SUCCESS_WITH_INFO {
+                    any_info = true;
+                }
+                let bound = stmt.bound_cols.clone();
+                let mut row_status = SQL_ROW_SUCCESS;
+                for col in &bound {
+                    let (target_value, str_len_or_ind) = row_slot_pointers(stmt, col, row_idx);
+                    match get_data(stmt, col.col_number, col.target_type, target_value, col.buffer_length, str_len_or_ind) {
+                        SQL_SUCCESS_WITH_INFO => {
+                            any_info = true;
+                            if row_status == SQL_ROW_SUCCESS {
+                                row_status = SQL_ROW_SUCCESS_WITH_INFO;
+                            }
+                        }
+                        SQL_ERROR => {
+                            any_error = true;
+                            row_status = SQL_ROW_ERROR;
+                        }
+                        _ => {}
+                    }
+                }
+                statuses.push(row_status);
+            }
+        }
+    }
+
+    let fetched = statuses.len();
+    if !stmt.row_status_ptr.is_null() {
+        unsafe {
+            for (i, status) in statuses.iter().enumerate() {
+                *stmt.row_status_ptr.add(i) = *status as SQLUSMALLINT;
+            }
+            for i in fetched..rowset_size {
+                *stmt.row_status_ptr.add(i) = SQL_ROW_NOROW as SQLUSMALLINT;
+            }
+        }
+    }
+    if !stmt.rows_fetched_ptr.is_null() {
+        unsafe {
+            *stmt.rows_fetched_ptr = fetched as SQLULEN;
+        }
+    }
+
+    if fetched == 0 {
+        SQL_NO_DATA
+    } else if any_error || any_info {
+        SQL_SUCCESS_WITH_INFO
+    } else {
+        SQL_SUCCESS
+    }
+}
+
+/// Compute the per-row target-value/indicator pointers for a bound column,
+/// honoring `SQL_ATTR_ROW_BIND_TYPE` (column-wise vs. row-wise struct stride).
+fn row_slot_pointers(stmt: &Statement, col: &BoundColumn, row_idx: usize) -> (SQLPOINTER, *mut SQLLEN) {
+    if stmt.row_bind_type == SQL_BIND_BY_COLUMN as SQLULEN {
+        let target_value = unsafe {
+            (col.target_value as *mut u8).add(row_idx * col.buffer_length.max(0) as usize) as SQLPOINTER
+        };
+        let str_len_or_ind = if col.str_len_or_ind.is_null() {
+            std::ptr::null_mut()
+        } else {
+            unsafe { col.str_len_or_ind.add(row_idx) }
+        };
+        (target_value, str_len_or_ind)
+    } else {
+        let stride = row_idx * stmt.row_bind_type as usize;
+        let target_value = unsafe { (col.target_value as *mut u8).add(stride) as SQLPOINTER };
+        let str_len_or_ind = if col.str_len_or_ind.is_null() {
+            std::ptr::null_mut()
+        } else {
+            unsafe { (col.str_len_or_ind as *mut u8).add(stride) as *mut SQLLEN }
+        };
+        (target_value, str_len_or_ind)
+    }
+}
+
+fn fetch_one_row(stmt: &mut Statement) -> SQLRETURN {
     if !stmt.executed {
         return SQL_ERROR;
     }
@@ -84,6 +189,14 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
     stmt.read_offsets.clear();
 
     if stmt.streaming {
+        // SQL_ATTR_MAX_ROWS caps rows returned to the caller; once hit, stop
+        // handing out more without touching `streaming` so the remainder of
+        // the result set is still drained (by `exec_direct`) the next time
+        // this statement is re-executed.
+        if stmt.max_rows != 0 && stmt.rows_fetched >= stmt.max_rows as u64 {
+            return SQL_NO_DATA;
+        }
+
         // If prefetch buffer is empty and no terminal state, fill it
         if stmt.prefetch_buffer.is_empty() && stmt.prefetch_done.is_none() {
             let conn = unsafe { &mut *stmt.conn };
@@ -97,9 +210,15 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
             let string_buf = &mut stmt.stream_string_buf;
             let bytes_buf = &mut stmt.stream_bytes_buf;
             let prefetch_buffer = &mut stmt.prefetch_buffer;
+            // SQL_ATTR_ROW_ARRAY_SIZE is a floor, not a cap: a block fetch
+            // shouldn't need a second round trip just to fill one call's
+            // rowset, so prefetch at least that many rows per round.
+            let batch_size = stmt.prefetch_rows.max(stmt.row_array_size).max(1);
+            let byte_budget = stmt.prefetch_byte_budget;
+            let mut buffered_bytes = 0usize;
 
             let terminal = runtime::block_on(async {
-                for _ in 0..256 {
+                for _ in 0..batch_size {
                     row_buf.clear();
                     let mut writer = SingleRowWriter {
                         row: &mut row_buf,
@@ -111,7 +230,15 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
                     {
                         Ok(BatchFetchResult::Row) => {
                             info_msgs.extend(writer.info_messages);
+                            buffered_bytes += row_buf.iter().map(CellValue::approx_byte_size).sum::<usize>();
                             prefetch_buffer.push_back(std::mem::replace(&mut row_buf, Vec::new()));
+                            // Stop filling once the approximate memory budget
+                            // is hit, even if the row-count batch isn't full
+                            // yet — wide/LOB-bearing rows shouldn't be able to
+                            // force unbounded buffering.
+                            if buffered_bytes >= byte_budget {
+                                break;
+                            }
                         }
                         Ok(BatchFetchResult::Done(_)) => {
                             info_msgs.extend(writer.info_messages);
@@ -127,7 +254,7 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
                         }
                     }
                 }
-                None // filled 256 rows, no terminal yet
+                None // filled a full batch (or hit the byte budget), no terminal yet
             });
 
             // Transfer info messages
@@ -148,6 +275,7 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
                 stmt.rows.clear();
                 stmt.rows.push(row);
                 stmt.row_index = 0;
+                stmt.rows_fetched += 1;
                 SQL_SUCCESS
             }
             None => {
@@ -185,6 +313,231 @@ pub fn fetch(stmt: &mut Statement) -> SQLRETURN {
     }
 }
 
+/// Reposition a buffered (non-streaming, e.g. `SQL_CURSOR_STATIC`) result set
+/// per `SQLFetchScroll`'s orientation/offset and land on that row exactly as
+/// `fetch_one_row` + the bound-column copy in `fetch_block` would for
+/// `SQL_FETCH_NEXT`. Forward-only (streaming) cursors only support
+/// `SQL_FETCH_NEXT`, which just delegates to the existing `fetch`.
+pub fn fetch_scroll(stmt: &mut Statement, orientation: SQLSMALLINT, offset: SQLLEN) -> SQLRETURN {
+    if orientation == SQL_FETCH_NEXT {
+        return fetch(stmt);
+    }
+    if stmt.streaming || !stmt.executed {
+        return SQL_ERROR;
+    }
+
+    let len = stmt.rows.len() as SQLLEN;
+    let new_index = match orientation {
+        SQL_FETCH_PRIOR => stmt.row_index - 1,
+        SQL_FETCH_FIRST => 0,
+        SQL_FETCH_LAST => len - 1,
+        SQL_FETCH_ABSOLUTE => {
+            if offset > 0 {
+                offset - 1
+            } else if offset < 0 {
+                len + offset
+            } else {
+                // SQL_FETCH_ABSOLUTE with offset 0 positions before the first row.
+                -1
+            }
+        }
+        SQL_FETCH_RELATIVE => stmt.row_index + offset,
+        _ => return SQL_ERROR,
+    };
+
+    if new_index < 0 || new_index >= len {
+        stmt.row_index = if new_index < 0 { -1 } else { len };
+        return SQL_NO_DATA;
+    }
+
+    stmt.row_index = new_index;
+    stmt.read_offsets.clear();
+
+    if stmt.bound_cols.is_empty() {
+        return SQL_SUCCESS;
+    }
+
+    let bound = stmt.bound_cols.clone();
+    let mut any_info = false;
+    let mut any_error = false;
+    for col in &bound {
+        let (target_value, str_len_or_ind) = row_slot_pointers(stmt, col, 0);
+        match get_data(
+            stmt,
+            col.col_number,
+            col.target_type,
+            target_value,
+            col.buffer_length,
+            str_len_or_ind,
+        ) {
+            SQL_SUCCESS_WITH_INFO => any_info = true,
+            SQL_ERROR => any_error = true,
+            _ => {}
+        }
+    }
+    if !stmt.rows_fetched_ptr.is_null() {
+        unsafe {
+            *stmt.rows_fetched_ptr = 1;
+        }
+    }
+    if !stmt.row_status_ptr.is_null() {
+        let status = if any_error {
+            SQL_ROW_ERROR
+        } else if any_info {
+            SQL_ROW_SUCCESS_WITH_INFO
+        } else {
+            SQL_ROW_SUCCESS
+        };
+        unsafe {
+            *stmt.row_status_ptr = status as SQLUSMALLINT;
+        }
+    }
+
+    if any_error {
+        SQL_ERROR
+    } else if any_info {
+        SQL_SUCCESS_WITH_INFO
+    } else {
+        SQL_SUCCESS
+    }
+}
+
+/// Advances to the next result set of a multi-statement batch, discarding
+/// whatever of the current one hasn't been consumed. Drives the streaming
+/// path by draining to the `MoreResults` terminal `fetch_one_row` already
+/// detects (but previously discarded), then replays the same
+/// columns/rows/row_count setup `exec_direct` does for a fresh execute. For
+/// the buffered (scrollable-cursor) path it just pops `pending_result_sets`.
+pub fn more_results(stmt: &mut Statement) -> SQLRETURN {
+    stmt.diagnostics.clear();
+
+    if !stmt.streaming {
+        return if stmt.pending_result_sets.is_empty() {
+            stmt.columns = Vec::new();
+            stmt.rows = Vec::new();
+            stmt.row_index = -1;
+            SQL_NO_DATA
+        } else {
+            let mut next = stmt.pending_result_sets.remove(0);
+            let rows = next.into_rows();
+            stmt.row_count = rows.len() as SQLLEN;
+            stmt.columns = next.columns;
+            stmt.rows = rows;
+            stmt.row_index = -1;
+            SQL_SUCCESS
+        };
+    }
+
+    let conn = unsafe { &mut *stmt.conn };
+    let client = match conn.client.as_mut() {
+        Some(c) => c,
+        None => {
+            stmt.streaming = false;
+            return SQL_NO_DATA;
+        }
+    };
+
+    stmt.prefetch_buffer.clear();
+    let terminal = match stmt.prefetch_done.take() {
+        Some(t) => t,
+        None => {
+            let string_buf = &mut stmt.stream_string_buf;
+            let bytes_buf = &mut stmt.stream_bytes_buf;
+            runtime::block_on(async {
+                loop {
+                    let mut discard = Vec::new();
+                    let mut writer = SingleRowWriter {
+                        row: &mut discard,
+                        info_messages: Vec::new(),
+                    };
+                    match client.batch_fetch_row(&mut writer, string_buf, bytes_buf).await {
+                        Ok(BatchFetchResult::Row) => continue,
+                        Ok(BatchFetchResult::Done(_)) => break PrefetchTerminal::Done,
+                        Ok(BatchFetchResult::MoreResults) => break PrefetchTerminal::MoreResults,
+                        Err(e) => break PrefetchTerminal::Error(e.to_string()),
+                    }
+                }
+            })
+        }
+    };
+
+    match terminal {
+        PrefetchTerminal::MoreResults => {
+            let mut rows_affected = 0u64;
+            let result = runtime::block_on(async {
+                client
+                    .batch_next_resultset(&mut rows_affected)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+            match result {
+                Ok(columns) => {
+                    stmt.columns = columns
+                        .iter()
+                        .map(|c| {
+                            let (sql_type, size, decimal_digits, nullable, ss_type) =
+                                sql_type_from_column(c);
+                            let (base_table, base_column, schema, catalog) = column_provenance(c);
+                            ColumnDesc {
+                                name: c.name().to_string(),
+                                sql_type,
+                                size,
+                                decimal_digits,
+                                nullable,
+                                ss_type,
+                                base_table,
+                                base_column,
+                                schema,
+                                catalog,
+                            }
+                        })
+                        .collect();
+                    stmt.rows = Vec::new();
+                    stmt.row_count = if stmt.columns.is_empty() {
+                        if rows_affected == 0 {
+                            -1
+                        } else {
+                            rows_affected as SQLLEN
+                        }
+                    } else {
+                        -1
+                    };
+                    stmt.row_index = -1;
+                    stmt.rows_fetched = 0;
+                    stmt.read_offsets.clear();
+                    stmt.streaming = true;
+                    SQL_SUCCESS
+                }
+                Err(msg) => {
+                    stmt.streaming = false;
+                    stmt.diagnostics.push(DiagRecord {
+                        state: "HY000".to_string(),
+                        native_error: 0,
+                        message: msg,
+                    });
+                    SQL_ERROR
+                }
+            }
+        }
+        PrefetchTerminal::Done => {
+            stmt.streaming = false;
+            stmt.columns = Vec::new();
+            stmt.rows = Vec::new();
+            stmt.row_index = -1;
+            SQL_NO_DATA
+        }
+        PrefetchTerminal::Error(msg) => {
+            stmt.streaming = false;
+            stmt.diagnostics.push(DiagRecord {
+                state: "HY000".to_string(),
+                native_error: 0,
+                message: msg,
+            });
+            SQL_ERROR
+        }
+    }
+}
+
 /// Helper: write a fixed-size numeric value to the target buffer
 unsafe fn write_fixed<T: Copy>(
     target_value: SQLPOINTER,
@@ -218,6 +571,88 @@ fn cell_to_i64(cell: &CellValue) -> i64 {
     }
 }
 
+/// Express a float as a scaled integer (`value * 10^scale`, rounded), using
+/// the bound column's `decimal_digits` as the scale when one is known —
+/// there's no ARD/`SQLSetDescField` machinery in this driver to ask the
+/// application for an explicit scale, so the source column is the next best
+/// source of truth.
+fn float_to_scaled_i128(v: f64, col: Option<&ColumnDesc>) -> (u8, i128) {
+    let scale = col.map(|c| c.decimal_digits.max(0) as u8).unwrap_or(0);
+    let scaled = v * 10f64.powi(scale as i32);
+    (scale, scaled.round() as i128)
+}
+
+/// Convert a cell to the `SQL_NUMERIC_STRUCT` layout `SQL_C_NUMERIC` bindings
+/// expect. Decimals already carry an exact unscaled magnitude
+/// (`CellValue::to_numeric_struct`); everything else is first expressed as a
+/// scaled `i128` and packed the same way. Returns whether the magnitude
+/// overflowed the struct's 16-byte `val` array.
+fn cell_to_numeric(cell: &CellValue, col: Option<&ColumnDesc>) -> (SqlNumericStruct, bool) {
+    if let Some(ns) = cell.to_numeric_struct() {
+        return (ns, false);
+    }
+
+    let (scale, signed) = match cell {
+        CellValue::F32(v) => float_to_scaled_i128(*v as f64, col),
+        CellValue::F64(v) => float_to_scaled_i128(*v, col),
+        _ => (0, cell_to_i64(cell) as i128),
+    };
+
+    let sign = if signed < 0 { 0 } else { 1 };
+    let mut magnitude = signed.unsigned_abs();
+    let mut val = [0u8; 16];
+    for byte in val.iter_mut() {
+        *byte = (magnitude % 256) as u8;
+        magnitude /= 256;
+    }
+    let overflowed = magnitude != 0;
+
+    let precision = col.map(|c| c.size as u8).unwrap_or(38);
+    (
+        SqlNumericStruct {
+            precision,
+            scale: scale as i8,
+            sign,
+            val,
+        },
+        overflowed,
+    )
+}
+
+/// Push a diagnostic for a lossy `SQLGetData` conversion and return the
+/// SQLRETURN the caller should report for it — the one piece of plumbing
+/// shared by every conversion arm that can silently lose or reject data.
+fn push_conversion_diag(stmt: &mut Statement, state: &str, message: &str, ret: SQLRETURN) -> SQLRETURN {
+    stmt.diagnostics.push(DiagRecord {
+        state: state.to_string(),
+        native_error: 0,
+        message: message.to_string(),
+    });
+    ret
+}
+
+/// Express a cell as an `f64` plus whether a nonzero fractional part would
+/// be dropped by truncating it toward zero, for the narrowing integer arms
+/// (`SQL_C_LONG`/`SQL_C_SHORT`/`SQL_C_UTINYINT`). `None` means the source
+/// can't be parsed as a number at all (an invalid cast, not a truncation).
+fn cell_as_integral(cell: &CellValue) -> Option<(f64, bool)> {
+    match cell {
+        CellValue::Bool(v) => Some(((*v as i64) as f64, false)),
+        CellValue::U8(v) => Some((*v as f64, false)),
+        CellValue::I16(v) => Some((*v as f64, false)),
+        CellValue::I32(v) => Some((*v as f64, false)),
+        CellValue::I64(v) => Some((*v as f64, false)),
+        CellValue::F32(v) => Some((*v as f64, v.fract() != 0.0)),
+        CellValue::F64(v) => Some((*v, v.fract() != 0.0)),
+        CellValue::Decimal { value, scale, .. } => {
+            let f = *value as f64 / 10f64.powi(*scale as i32);
+            Some((f, f.fract() != 0.0))
+        }
+        CellValue::String(s) => s.trim().parse::<f64>().ok().map(|f| (f, f.fract() != 0.0)),
+        _ => None,
+    }
+}
+
 fn cell_to_f64(cell: &CellValue) -> f64 {
     match cell {
         CellValue::Bool(v) => {
@@ -239,6 +674,28 @@ fn cell_to_f64(cell: &CellValue) -> f64 {
     }
 }
 
+/// Picks the natural C type for `SQL_C_DEFAULT`, based on the column's wire
+/// `sql_type` — shared by `get_data` and `SQLGetDataW`'s char-vs-native
+/// dispatch so both honor the same default.
+pub(crate) fn default_c_type(stmt: &Statement, col_idx: usize) -> SQLSMALLINT {
+    if col_idx >= stmt.columns.len() {
+        return SQL_C_CHAR;
+    }
+    match stmt.columns[col_idx].sql_type {
+        SQL_INTEGER | SQL_SMALLINT | SQL_TINYINT => SQL_C_LONG,
+        SQL_BIGINT => SQL_C_SBIGINT,
+        SQL_DOUBLE | SQL_FLOAT => SQL_C_DOUBLE,
+        SQL_REAL => SQL_C_FLOAT,
+        SQL_BIT => SQL_C_BIT,
+        SQL_TYPE_TIMESTAMP => SQL_C_TYPE_TIMESTAMP,
+        SQL_TYPE_DATE => SQL_C_TYPE_DATE,
+        SQL_TYPE_TIME => SQL_C_TYPE_TIME,
+        SQL_BINARY | SQL_VARBINARY | SQL_LONGVARBINARY => SQL_C_BINARY,
+        SQL_GUID => SQL_C_GUID,
+        _ => SQL_C_CHAR,
+    }
+}
+
 pub fn get_data(
     stmt: &mut Statement,
     col: SQLUSMALLINT,
@@ -250,6 +707,7 @@ pub fn get_data(
     if stmt.row_index < 0 || stmt.row_index as usize >= stmt.rows.len() {
         return SQL_ERROR;
     }
+    let conn = unsafe { &*stmt.conn };
     let row = &stmt.rows[stmt.row_index as usize];
     let col_idx = (col as usize).wrapping_sub(1); // 1-based to 0-based
     if col_idx >= row.len() {
@@ -276,63 +734,76 @@ pub fn get_data(
 
     // Determine effective target type
     let eff_type = if target_type == SQL_C_DEFAULT {
-        if col_idx < stmt.columns.len() {
-            match stmt.columns[col_idx].sql_type {
-                SQL_INTEGER => SQL_C_LONG,
-                SQL_SMALLINT => SQL_C_SHORT,
-                SQL_BIGINT => SQL_C_SBIGINT,
-                SQL_DOUBLE | SQL_FLOAT => SQL_C_DOUBLE,
-                SQL_REAL => SQL_C_FLOAT,
-                SQL_BIT => SQL_C_BIT,
-                SQL_TYPE_TIMESTAMP => SQL_C_TYPE_TIMESTAMP,
-                SQL_TYPE_DATE => SQL_C_TYPE_DATE,
-                SQL_TYPE_TIME => SQL_C_TYPE_TIME,
-                SQL_BINARY | SQL_VARBINARY | SQL_LONGVARBINARY => SQL_C_BINARY,
-                SQL_GUID => SQL_C_GUID,
-                SQL_TINYINT => SQL_C_UTINYINT,
-                _ => SQL_C_CHAR,
-            }
-        } else {
-            SQL_C_CHAR
-        }
+        default_c_type(stmt, col_idx)
     } else {
         target_type
     };
 
     match eff_type {
-        SQL_C_LONG | SQL_C_SLONG => {
-            let v: i32 = match cell {
-                CellValue::I32(v) => *v,
-                CellValue::Bool(v) => *v as i32,
-                CellValue::U8(v) => *v as i32,
-                CellValue::I16(v) => *v as i32,
-                _ => cell_to_i64(cell) as i32,
-            };
-            unsafe {
-                write_fixed(
-                    target_value,
-                    str_len_or_ind,
-                    v,
-                    &mut stmt.read_offsets,
-                    col_idx,
-                )
+        SQL_C_LONG | SQL_C_SLONG => match cell_as_integral(cell) {
+            None => push_conversion_diag(
+                stmt,
+                "22018",
+                "Invalid character value for cast specification",
+                SQL_ERROR,
+            ),
+            Some((v, _)) if v > i32::MAX as f64 || v < i32::MIN as f64 => {
+                push_conversion_diag(stmt, "22003", "Numeric value out of range", SQL_ERROR)
             }
-        }
-        SQL_C_SHORT => {
-            let v: i16 = match cell {
-                CellValue::I16(v) => *v,
-                _ => cell_to_i64(cell) as i16,
-            };
-            unsafe {
-                write_fixed(
-                    target_value,
-                    str_len_or_ind,
-                    v,
-                    &mut stmt.read_offsets,
-                    col_idx,
-                )
+            Some((v, fractional)) => {
+                let ret = unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v as i32,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                };
+                if fractional {
+                    push_conversion_diag(
+                        stmt,
+                        "22003",
+                        "Fractional part of the numeric value was truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    )
+                } else {
+                    ret
+                }
             }
-        }
+        },
+        SQL_C_SHORT => match cell_as_integral(cell) {
+            None => push_conversion_diag(
+                stmt,
+                "22018",
+                "Invalid character value for cast specification",
+                SQL_ERROR,
+            ),
+            Some((v, _)) if v > i16::MAX as f64 || v < i16::MIN as f64 => {
+                push_conversion_diag(stmt, "22003", "Numeric value out of range", SQL_ERROR)
+            }
+            Some((v, fractional)) => {
+                let ret = unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v as i16,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                };
+                if fractional {
+                    push_conversion_diag(
+                        stmt,
+                        "22003",
+                        "Fractional part of the numeric value was truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    )
+                } else {
+                    ret
+                }
+            }
+        },
         SQL_C_SBIGINT => {
             let v: i64 = match cell {
                 CellValue::I64(v) => *v,
@@ -349,33 +820,54 @@ pub fn get_data(
             }
         }
         SQL_C_DOUBLE => {
-            let v: f64 = match cell {
-                CellValue::F64(v) => *v,
-                _ => cell_to_f64(cell),
+            let v: Option<f64> = match cell {
+                CellValue::F64(v) => Some(*v),
+                CellValue::String(s) => s.trim().parse().ok(),
+                _ => Some(cell_to_f64(cell)),
             };
-            unsafe {
-                write_fixed(
-                    target_value,
-                    str_len_or_ind,
-                    v,
-                    &mut stmt.read_offsets,
-                    col_idx,
-                )
+            match v {
+                None => push_conversion_diag(
+                    stmt,
+                    "22018",
+                    "Invalid character value for cast specification",
+                    SQL_ERROR,
+                ),
+                Some(v) => unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                },
             }
         }
         SQL_C_FLOAT => {
-            let v: f32 = match cell {
-                CellValue::F32(v) => *v,
-                _ => cell_to_f64(cell) as f32,
+            let v: Option<f64> = match cell {
+                CellValue::F32(v) => Some(*v as f64),
+                CellValue::String(s) => s.trim().parse().ok(),
+                _ => Some(cell_to_f64(cell)),
             };
-            unsafe {
-                write_fixed(
-                    target_value,
-                    str_len_or_ind,
-                    v,
-                    &mut stmt.read_offsets,
-                    col_idx,
-                )
+            match v {
+                None => push_conversion_diag(
+                    stmt,
+                    "22018",
+                    "Invalid character value for cast specification",
+                    SQL_ERROR,
+                ),
+                Some(v) if v.abs() > f32::MAX as f64 => {
+                    push_conversion_diag(stmt, "22003", "Numeric value out of range", SQL_ERROR)
+                }
+                Some(v) => unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v as f32,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                },
             }
         }
         SQL_C_BIT => {
@@ -419,21 +911,70 @@ pub fn get_data(
                 )
             }
         }
-        SQL_C_UTINYINT | SQL_C_STINYINT => {
-            let v: u8 = match cell {
-                CellValue::U8(v) => *v,
-                _ => cell_to_i64(cell) as u8,
-            };
-            unsafe {
-                write_fixed(
-                    target_value,
-                    str_len_or_ind,
-                    v,
-                    &mut stmt.read_offsets,
-                    col_idx,
-                )
+        SQL_C_UTINYINT => match cell_as_integral(cell) {
+            None => push_conversion_diag(
+                stmt,
+                "22018",
+                "Invalid character value for cast specification",
+                SQL_ERROR,
+            ),
+            Some((v, _)) if v > u8::MAX as f64 || v < u8::MIN as f64 => {
+                push_conversion_diag(stmt, "22003", "Numeric value out of range", SQL_ERROR)
             }
-        }
+            Some((v, fractional)) => {
+                let ret = unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v as u8,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                };
+                if fractional {
+                    push_conversion_diag(
+                        stmt,
+                        "22003",
+                        "Fractional part of the numeric value was truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    )
+                } else {
+                    ret
+                }
+            }
+        },
+        SQL_C_STINYINT => match cell_as_integral(cell) {
+            None => push_conversion_diag(
+                stmt,
+                "22018",
+                "Invalid character value for cast specification",
+                SQL_ERROR,
+            ),
+            Some((v, _)) if v > i8::MAX as f64 || v < i8::MIN as f64 => {
+                push_conversion_diag(stmt, "22003", "Numeric value out of range", SQL_ERROR)
+            }
+            Some((v, fractional)) => {
+                let ret = unsafe {
+                    write_fixed(
+                        target_value,
+                        str_len_or_ind,
+                        v as i8,
+                        &mut stmt.read_offsets,
+                        col_idx,
+                    )
+                };
+                if fractional {
+                    push_conversion_diag(
+                        stmt,
+                        "22003",
+                        "Fractional part of the numeric value was truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    )
+                } else {
+                    ret
+                }
+            }
+        },
         SQL_C_WCHAR => {
             // Fast path: if we already have UTF-16, skip encoding entirely
             let utf16: std::borrow::Cow<[u16]> = match cell {
@@ -471,7 +1012,16 @@ pub fn get_data(
 
             if !target_value.is_null() && buffer_length > 0 {
                 let buf_u16_cap = (buffer_length as usize) / 2;
-                let copy_count = std::cmp::min(remaining_u16.len(), buf_u16_cap.saturating_sub(1));
+                let mut copy_count = std::cmp::min(remaining_u16.len(), buf_u16_cap.saturating_sub(1));
+                // Never split a surrogate pair across a buffer boundary: if
+                // truncation would land right after a high surrogate, back
+                // off one unit so the pair carries over to the next call.
+                if copy_count > 0
+                    && copy_count < remaining_u16.len()
+                    && (0xD800..=0xDBFF).contains(&remaining_u16[copy_count - 1])
+                {
+                    copy_count -= 1;
+                }
                 let dest = target_value as *mut u16;
                 unsafe {
                     ptr::copy_nonoverlapping(remaining_u16.as_ptr(), dest, copy_count);
@@ -479,16 +1029,25 @@ pub fn get_data(
                 }
                 stmt.read_offsets[col_idx] = offset + copy_count;
                 if remaining_u16.len() > copy_count {
-                    return SQL_SUCCESS_WITH_INFO;
+                    return push_conversion_diag(
+                        stmt,
+                        "01004",
+                        "String data, right truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    );
                 }
             }
             stmt.read_offsets[col_idx] = 0;
             SQL_SUCCESS
         }
         SQL_C_TYPE_TIMESTAMP => {
+            // Date/time column descriptors carry no fractional scale
+            // (`decimal_digits` stays 0), so 0 is treated as unknown rather
+            // than as a zero-digit scale that would drop every fraction.
+            let scale = stmt.columns.get(col_idx).map(|c| c.decimal_digits).filter(|&s| s > 0);
             let ts = match cell {
                 CellValue::DateTime { micros } => {
-                    let (year, month, day, h, mi, sec, millis) = micros_to_timestamp_parts(*micros);
+                    let (year, month, day, h, mi, sec, _) = micros_to_timestamp_parts(*micros);
                     SqlTimestampStruct {
                         year: year as i16,
                         month: month as u16,
@@ -496,11 +1055,16 @@ pub fn get_data(
                         hour: h as u16,
                         minute: mi as u16,
                         second: sec as u16,
-                        fraction: millis * 1_000_000, // millis -> nanoseconds
+                        fraction: cap_fraction_to_scale(sub_second_nanos(*micros), scale),
                     }
                 }
-                CellValue::DateTimeOffset { micros, .. } => {
-                    let (year, month, day, h, mi, sec, millis) = micros_to_timestamp_parts(*micros);
+                CellValue::DateTimeOffset { micros, offset_min } => {
+                    let local_micros = if conn.normalize_timestampoffset_local {
+                        apply_timezone_offset(*micros, *offset_min)
+                    } else {
+                        *micros
+                    };
+                    let (year, month, day, h, mi, sec, _) = micros_to_timestamp_parts(local_micros);
                     SqlTimestampStruct {
                         year: year as i16,
                         month: month as u16,
@@ -508,7 +1072,22 @@ pub fn get_data(
                         hour: h as u16,
                         minute: mi as u16,
                         second: sec as u16,
-                        fraction: millis * 1_000_000,
+                        fraction: cap_fraction_to_scale(sub_second_nanos(local_micros), scale),
+                    }
+                }
+                // A TIME column retrieved as SQL_C_TYPE_TIMESTAMP: the backend
+                // already hands us nanoseconds directly, so use them as-is
+                // instead of round-tripping through micros_to_timestamp_parts.
+                CellValue::Time { nanos } => {
+                    let total_secs = (*nanos / 1_000_000_000) as u32;
+                    SqlTimestampStruct {
+                        year: 0,
+                        month: 0,
+                        day: 0,
+                        hour: (total_secs / 3600) as u16,
+                        minute: ((total_secs % 3600) / 60) as u16,
+                        second: (total_secs % 60) as u16,
+                        fraction: cap_fraction_to_scale((*nanos % 1_000_000_000) as u32, scale),
                     }
                 }
                 CellValue::Date { .. } => {
@@ -535,7 +1114,7 @@ pub fn get_data(
         }
         SQL_C_TYPE_DATE => {
             let ts = match cell {
-                CellValue::DateTime { micros } | CellValue::DateTimeOffset { micros, .. } => {
+                CellValue::DateTime { micros } => {
                     let (year, month, day, ..) = micros_to_timestamp_parts(*micros);
                     SqlDateStruct {
                         year: year as i16,
@@ -543,6 +1122,19 @@ pub fn get_data(
                         day: day as u16,
                     }
                 }
+                CellValue::DateTimeOffset { micros, offset_min } => {
+                    let local_micros = if conn.normalize_timestampoffset_local {
+                        apply_timezone_offset(*micros, *offset_min)
+                    } else {
+                        *micros
+                    };
+                    let (year, month, day, ..) = micros_to_timestamp_parts(local_micros);
+                    SqlDateStruct {
+                        year: year as i16,
+                        month: month as u16,
+                        day: day as u16,
+                    }
+                }
                 CellValue::Date { .. } => {
                     let s = cell.to_string_repr().unwrap_or_default();
                     let ts = parse_timestamp(&s);
@@ -585,7 +1177,7 @@ pub fn get_data(
                         second: (total_secs % 60) as u16,
                     }
                 }
-                CellValue::DateTime { micros } | CellValue::DateTimeOffset { micros, .. } => {
+                CellValue::DateTime { micros } => {
                     let (_, _, _, h, mi, sec, _) = micros_to_timestamp_parts(*micros);
                     SqlTimeStruct {
                         hour: h as u16,
@@ -593,6 +1185,19 @@ pub fn get_data(
                         second: sec as u16,
                     }
                 }
+                CellValue::DateTimeOffset { micros, offset_min } => {
+                    let local_micros = if conn.normalize_timestampoffset_local {
+                        apply_timezone_offset(*micros, *offset_min)
+                    } else {
+                        *micros
+                    };
+                    let (_, _, _, h, mi, sec, _) = micros_to_timestamp_parts(local_micros);
+                    SqlTimeStruct {
+                        hour: h as u16,
+                        minute: mi as u16,
+                        second: sec as u16,
+                    }
+                }
                 _ => {
                     let s = cell.to_string_repr().unwrap_or_default();
                     let ts = parse_timestamp(&s);
@@ -616,11 +1221,18 @@ pub fn get_data(
             stmt.read_offsets[col_idx] = 0;
             SQL_SUCCESS
         }
+        // Chunked multi-call reads, mirroring SQL_C_WCHAR above and SQL_C_CHAR below:
+        // `read_offsets[col_idx]` tracks how much of the column has already
+        // been handed out so a caller can page through a large BLOB/VARBINARY
+        // value across repeated SQLGetData calls instead of one allocation.
         SQL_C_BINARY => {
             let bytes: Vec<u8> = match cell {
                 CellValue::Bytes(b) => b.clone(),
                 CellValue::Guid(g) => g.to_vec(),
                 _ => {
+                    // Non-binary cells only have a text representation; treat
+                    // it as hex-encoded bytes when it looks like hex, else as
+                    // the raw text bytes themselves.
                     let s = cell.to_string_repr().unwrap_or_default();
                     if s.chars().all(|c| c.is_ascii_hexdigit()) && s.len() % 2 == 0 {
                         hex_decode(&s)
@@ -662,7 +1274,101 @@ pub fn get_data(
                 }
                 stmt.read_offsets[col_idx] = offset + copy_len;
                 if remaining.len() > copy_len {
-                    return SQL_SUCCESS_WITH_INFO;
+                    return push_conversion_diag(
+                        stmt,
+                        "01004",
+                        "String data, right truncated",
+                        SQL_SUCCESS_WITH_INFO,
+                    );
+                }
+            }
+            stmt.read_offsets[col_idx] = 0;
+            SQL_SUCCESS
+        }
+        SQL_C_NUMERIC => {
+            let (numeric, overflowed) = cell_to_numeric(cell, stmt.columns.get(col_idx));
+            if !target_value.is_null() {
+                unsafe {
+                    *(target_value as *mut SqlNumericStruct) = numeric;
+                }
+            }
+            if !str_len_or_ind.is_null() {
+                unsafe {
+                    *str_len_or_ind = std::mem::size_of::<SqlNumericStruct>() as SQLLEN;
+                }
+            }
+            stmt.read_offsets[col_idx] = 0;
+            if overflowed {
+                stmt.diagnostics.push(DiagRecord {
+                    state: "22003".to_string(),
+                    native_error: 0,
+                    message: "Numeric value out of range".to_string(),
+                });
+                SQL_SUCCESS_WITH_INFO
+            } else {
+                SQL_SUCCESS
+            }
+        }
+        SQL_C_SS_TIMESTAMPOFFSET => {
+            // Unlike SQL_C_TYPE_TIMESTAMP, this SQL Server extension always
+            // reports the value as stored plus its offset, so applications can
+            // recover the instant losslessly — SQL_ATTR_FURBALL_DATETIMEOFFSET_LOCAL
+            // doesn't apply here.
+            let off = match cell {
+                CellValue::DateTimeOffset { micros, offset_min } => {
+                    let (year, month, day, h, mi, sec, _) = micros_to_timestamp_parts(*micros);
+                    let sign: i16 = if *offset_min < 0 { -1 } else { 1 };
+                    let abs_offset = offset_min.unsigned_abs();
+                    SqlSsTimestampOffsetStruct {
+                        year: year as i16,
+                        month: month as u16,
+                        day: day as u16,
+                        hour: h as u16,
+                        minute: mi as u16,
+                        second: sec as u16,
+                        fraction: sub_second_nanos(*micros),
+                        timezone_hour: sign * (abs_offset / 60) as i16,
+                        timezone_minute: (abs_offset % 60) as i16,
+                    }
+                }
+                CellValue::DateTime { micros } => {
+                    let (year, month, day, h, mi, sec, _) = micros_to_timestamp_parts(*micros);
+                    SqlSsTimestampOffsetStruct {
+                        year: year as i16,
+                        month: month as u16,
+                        day: day as u16,
+                        hour: h as u16,
+                        minute: mi as u16,
+                        second: sec as u16,
+                        fraction: sub_second_nanos(*micros),
+                        timezone_hour: 0,
+                        timezone_minute: 0,
+                    }
+                }
+                _ => {
+                    let s = cell.to_string_repr().unwrap_or_default();
+                    let ts = parse_timestamp(&s);
+                    SqlSsTimestampOffsetStruct {
+                        year: ts.year,
+                        month: ts.month,
+                        day: ts.day,
+                        hour: ts.hour,
+                        minute: ts.minute,
+                        second: ts.second,
+                        fraction: ts.fraction,
+                        timezone_hour: 0,
+                        timezone_minute: 0,
+                    }
+                }
+            };
+            if !target_value.is_null() {
+                unsafe {
+                    *(target_value as *mut SqlSsTimestampOffsetStruct) = off;
+                }
+            }
+            if !str_len_or_ind.is_null() {
+                unsafe {
+                    *str_len_or_ind = std::mem::size_of::<SqlSsTimestampOffsetStruct>() as SQLLEN;
                 }
             }
             stmt.read_offsets[col_idx] = 0;
@@ -670,15 +1376,7 @@ pub fn get_data(
         }
         SQL_C_GUID => {
             let guid = match cell {
-                CellValue::Guid(bytes) => SqlGuid {
-                    data1: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
-                    data2: u16::from_be_bytes([bytes[4], bytes[5]]),
-                    data3: u16::from_be_bytes([bytes[6], bytes[7]]),
-                    data4: [
-                        bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14],
-                        bytes[15],
-                    ],
-                },
+                CellValue::Guid(bytes) => guid_from_bytes(bytes, conn.guid_byte_order),
                 _ => {
                     let s = cell.to_string_repr().unwrap_or_default();
                     parse_guid(&s)
@@ -697,6 +1395,61 @@ pub fn get_data(
             stmt.read_offsets[col_idx] = 0;
             SQL_SUCCESS
         }
+        SQL_C_INTERVAL_YEAR
+        | SQL_C_INTERVAL_MONTH
+        | SQL_C_INTERVAL_DAY
+        | SQL_C_INTERVAL_HOUR
+        | SQL_C_INTERVAL_MINUTE
+        | SQL_C_INTERVAL_SECOND
+        | SQL_C_INTERVAL_YEAR_TO_MONTH
+        | SQL_C_INTERVAL_DAY_TO_HOUR
+        | SQL_C_INTERVAL_DAY_TO_MINUTE
+        | SQL_C_INTERVAL_DAY_TO_SECOND
+        | SQL_C_INTERVAL_HOUR_TO_MINUTE
+        | SQL_C_INTERVAL_HOUR_TO_SECOND
+        | SQL_C_INTERVAL_MINUTE_TO_SECOND => {
+            let s = cell.to_string_repr().unwrap_or_default();
+            let parsed = parse_interval(&s);
+            let interval_type = interval_type_for_c_type(eff_type);
+            let year_month = matches!(
+                eff_type,
+                SQL_C_INTERVAL_YEAR | SQL_C_INTERVAL_MONTH | SQL_C_INTERVAL_YEAR_TO_MONTH
+            );
+            let interval = SqlIntervalStruct {
+                interval_type,
+                interval_sign: if parsed.negative { SQL_TRUE as SQLSMALLINT } else { SQL_FALSE as SQLSMALLINT },
+                intval: if year_month {
+                    SqlIntervalValue {
+                        year_month: SqlYearMonthStruct {
+                            year: parsed.years,
+                            month: parsed.months,
+                        },
+                    }
+                } else {
+                    SqlIntervalValue {
+                        day_second: SqlDaySecondStruct {
+                            day: parsed.days,
+                            hour: parsed.hours,
+                            minute: parsed.minutes,
+                            second: parsed.seconds,
+                            fraction: parsed.fraction_nanos,
+                        },
+                    }
+                },
+            };
+            if !target_value.is_null() {
+                unsafe {
+                    *(target_value as *mut SqlIntervalStruct) = interval;
+                }
+            }
+            if !str_len_or_ind.is_null() {
+                unsafe {
+                    *str_len_or_ind = std::mem::size_of::<SqlIntervalStruct>() as SQLLEN;
+                }
+            }
+            stmt.read_offsets[col_idx] = 0;
+            SQL_SUCCESS
+        }
         _ => {
             // SQL_C_CHAR or unknown: return as ANSI string with chunked read support
             let val = cell.to_string_repr().unwrap_or_default();
@@ -785,7 +1538,7 @@ pub fn describe_col(
     }
     if !data_type.is_null() {
         unsafe {
-            *data_type = col.sql_type;
+            *data_type = col.ss_type.unwrap_or(col.sql_type);
         }
     }
     if !column_size.is_null() {
@@ -806,9 +1559,88 @@ pub fn describe_col(
     SQL_SUCCESS
 }
 
+/// Nanosecond fraction within the current second for a `micros`-since-epoch
+/// value, preserving full microsecond precision instead of the millisecond
+/// rounding `micros_to_timestamp_parts` applies for its `h:m:s` breakdown.
+fn sub_second_nanos(micros: i64) -> u32 {
+    (micros.rem_euclid(1_000_000) as u32) * 1_000
+}
+
+/// Shift a `DateTimeOffset`'s UTC `micros` by its zone offset, for the
+/// `SQL_ATTR_FURBALL_DATETIMEOFFSET_LOCAL`-enabled path where callers want the
+/// intended local wall-clock time rather than the stored UTC instant.
+fn apply_timezone_offset(micros: i64, offset_min: i16) -> i64 {
+    micros + (offset_min as i64) * 60_000_000
+}
+
+/// Zero out the nanosecond digits beyond a column's declared scale (e.g. a
+/// DATETIME2(3) column reports milliseconds, so digits past the third are
+/// noise introduced by our internal representation rather than real
+/// precision) so round-tripped TIME/DATETIME2 values match what the column
+/// actually declares. `None` (scale unknown, or >= 9) leaves `nanos` as-is.
+fn cap_fraction_to_scale(nanos: u32, scale: Option<SQLSMALLINT>) -> u32 {
+    match scale {
+        Some(s) if (0..9).contains(&s) => {
+            let divisor = 10u32.pow(9 - s as u32);
+            (nanos / divisor) * divisor
+        }
+        _ => nanos,
+    }
+}
+
+/// Split a trailing ISO-8601 timezone suffix (`Z`, `±HH:MM` or `±HHMM`) off a
+/// time string, returning the remainder plus the offset in signed minutes
+/// (`0` when no suffix is present, i.e. the value is treated as already UTC).
+fn parse_tz_offset_minutes(time_part: &str) -> (&str, i32) {
+    if let Some(stripped) = time_part.strip_suffix(['Z', 'z']) {
+        return (stripped, 0);
+    }
+    if let Some(idx) = time_part.rfind(['+', '-']) {
+        let (time_str, offset_str) = time_part.split_at(idx);
+        let sign = if offset_str.starts_with('-') { -1 } else { 1 };
+        let digits: String = offset_str[1..].chars().filter(|c| *c != ':').collect();
+        let (hh, mm) = if digits.len() >= 4 {
+            (digits[0..2].parse().unwrap_or(0), digits[2..4].parse().unwrap_or(0))
+        } else if digits.len() >= 2 {
+            (digits[0..2].parse().unwrap_or(0), 0)
+        } else {
+            (0, 0)
+        };
+        return (time_str, sign * (hh * 60 + mm));
+    }
+    (time_part, 0)
+}
+
+/// Howard Hinnant's `days_from_civil`: days since the Unix epoch for a
+/// proleptic-Gregorian (year, month, day).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // [0, 399]
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of `days_from_civil`: (year, month, day) for a day count since the
+/// Unix epoch.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let d = doy - (153 * mp + 2) / 5 + 1; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 }; // [1, 12]
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m as u32, d as u32)
+}
+
 fn parse_timestamp(s: &str) -> SqlTimestampStruct {
     let mut ts = SqlTimestampStruct::default();
-    // "YYYY-MM-DD HH:MM:SS.fff"
+    // "YYYY-MM-DD HH:MM:SS.fff[Z|±HH:MM]"
     let parts: Vec<&str> = s.splitn(2, [' ', 'T']).collect();
     if let Some(date_part) = parts.first() {
         let d: Vec<&str> = date_part.split('-').collect();
@@ -818,9 +1650,10 @@ fn parse_timestamp(s: &str) -> SqlTimestampStruct {
             ts.day = d[2].parse().unwrap_or(0);
         }
     }
+    let mut offset_minutes = 0;
     if let Some(time_part) = parts.get(1) {
-        // Strip timezone offset if present
-        let time_str = time_part.split(['+', '-']).next().unwrap_or(time_part);
+        let (time_str, offset) = parse_tz_offset_minutes(time_part);
+        offset_minutes = offset;
         let t: Vec<&str> = time_str.split(':').collect();
         if t.len() >= 3 {
             ts.hour = t[0].parse().unwrap_or(0);
@@ -836,6 +1669,23 @@ fn parse_timestamp(s: &str) -> SqlTimestampStruct {
             }
         }
     }
+
+    // Normalize to UTC: subtract the parsed offset from the civil time,
+    // handling date rollover via the day-count round trip above.
+    if offset_minutes != 0 {
+        let days = days_from_civil(ts.year as i64, ts.month as i64, ts.day as i64);
+        let total_minutes =
+            days * 1440 + ts.hour as i64 * 60 + ts.minute as i64 - offset_minutes as i64;
+        let new_days = total_minutes.div_euclid(1440);
+        let minute_of_day = total_minutes.rem_euclid(1440);
+        let (y, m, d) = civil_from_days(new_days);
+        ts.year = y as i16;
+        ts.month = m as u16;
+        ts.day = d as u16;
+        ts.hour = (minute_of_day / 60) as u16;
+        ts.minute = (minute_of_day % 60) as u16;
+    }
+
     ts
 }
 
@@ -849,6 +1699,186 @@ fn hex_decode(s: &str) -> Vec<u8> {
     bytes
 }
 
+/// Decoded fields for an `SQL_INTERVAL_STRUCT`, before they're sorted into the
+/// year-month or day-second half of its union depending on the requested
+/// `SQL_C_INTERVAL_*` type.
+#[derive(Default)]
+struct ParsedInterval {
+    negative: bool,
+    years: SQLUINTEGER,
+    months: SQLUINTEGER,
+    days: SQLUINTEGER,
+    hours: SQLUINTEGER,
+    minutes: SQLUINTEGER,
+    seconds: SQLUINTEGER,
+    fraction_nanos: SQLUINTEGER,
+}
+
+/// Accumulate a run of digits starting at `chars[*i]`, advancing `*i` past
+/// them, and parse it as a `SQLUINTEGER` (0 if no digits were present).
+fn take_digits(chars: &[char], i: &mut usize) -> SQLUINTEGER {
+    let start = *i;
+    while *i < chars.len() && chars[*i].is_ascii_digit() {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect::<String>().parse().unwrap_or(0)
+}
+
+/// Parse an ISO-8601 duration (`P1Y2M10DT2H30M10.5S`) into a `ParsedInterval`.
+/// `s` is the portion after a leading sign and the `P` designator.
+fn parse_iso8601_duration(s: &str) -> ParsedInterval {
+    let mut out = ParsedInterval::default();
+    let (date_part, time_part) = match s.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (s, None),
+    };
+
+    let chars: Vec<char> = date_part.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_ascii_digit() {
+            let n = take_digits(&chars, &mut i);
+            match chars.get(i) {
+                Some('Y') => out.years = n,
+                Some('M') => out.months = n,
+                Some('D') => out.days = n,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+
+    if let Some(time_part) = time_part {
+        let chars: Vec<char> = time_part.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i].is_ascii_digit() {
+                let whole = take_digits(&chars, &mut i);
+                let mut frac_nanos = 0u32;
+                if chars.get(i) == Some(&'.') {
+                    i += 1;
+                    let frac_start = i;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    let frac_str: String = chars[frac_start..i].iter().collect();
+                    frac_nanos = format!("{:0<9}", frac_str)[..9].parse().unwrap_or(0);
+                }
+                match chars.get(i) {
+                    Some('H') => out.hours = whole,
+                    Some('M') => out.minutes = whole,
+                    Some('S') => {
+                        out.seconds = whole;
+                        out.fraction_nanos = frac_nanos;
+                    }
+                    _ => {}
+                }
+            }
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Parse the common SQL Server interval text form, `[-]days HH:MM:SS[.fff]`
+/// (also tolerating a bare `HH:MM:SS` with no day component).
+fn parse_days_hms_duration(s: &str) -> ParsedInterval {
+    let mut out = ParsedInterval::default();
+    let (day_part, time_part) = match s.split_once(' ') {
+        Some((d, t)) => (Some(d), t),
+        None => (None, s),
+    };
+    if let Some(day_part) = day_part {
+        out.days = day_part.trim().parse().unwrap_or(0);
+    }
+
+    let time_parts: Vec<&str> = time_part.split(':').collect();
+    if let Some(h) = time_parts.first() {
+        out.hours = h.parse().unwrap_or(0);
+    }
+    if let Some(m) = time_parts.get(1) {
+        out.minutes = m.parse().unwrap_or(0);
+    }
+    if let Some(sec_field) = time_parts.get(2) {
+        let sec_parts: Vec<&str> = sec_field.split('.').collect();
+        out.seconds = sec_parts[0].parse().unwrap_or(0);
+        if let Some(frac) = sec_parts.get(1) {
+            out.fraction_nanos = format!("{:0<9}", frac)[..9].parse().unwrap_or(0);
+        }
+    }
+    out
+}
+
+/// Parse an interval value in either ISO-8601 duration form
+/// (`P1Y2M10DT2H30M10.5S`) or the `[-]days HH:MM:SS[.fff]` form SQL Server
+/// reports for `datetime`/`time` differences, with an optional leading sign
+/// shared by both.
+fn parse_interval(s: &str) -> ParsedInterval {
+    let s = s.trim();
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let mut parsed = if let Some(body) = rest.strip_prefix(['P', 'p']) {
+        parse_iso8601_duration(body)
+    } else {
+        parse_days_hms_duration(rest)
+    };
+    parsed.negative = negative;
+    parsed
+}
+
+/// Map a `SQL_C_INTERVAL_*` target type to the `SQL_IS_*` constant that
+/// identifies the interval's shape in `SQL_INTERVAL_STRUCT::interval_type`.
+fn interval_type_for_c_type(c_type: SQLSMALLINT) -> SQLSMALLINT {
+    match c_type {
+        SQL_C_INTERVAL_YEAR => SQL_IS_YEAR,
+        SQL_C_INTERVAL_MONTH => SQL_IS_MONTH,
+        SQL_C_INTERVAL_DAY => SQL_IS_DAY,
+        SQL_C_INTERVAL_HOUR => SQL_IS_HOUR,
+        SQL_C_INTERVAL_MINUTE => SQL_IS_MINUTE,
+        SQL_C_INTERVAL_SECOND => SQL_IS_SECOND,
+        SQL_C_INTERVAL_YEAR_TO_MONTH => SQL_IS_YEAR_TO_MONTH,
+        SQL_C_INTERVAL_DAY_TO_HOUR => SQL_IS_DAY_TO_HOUR,
+        SQL_C_INTERVAL_DAY_TO_MINUTE => SQL_IS_DAY_TO_MINUTE,
+        SQL_C_INTERVAL_DAY_TO_SECOND => SQL_IS_DAY_TO_SECOND,
+        SQL_C_INTERVAL_HOUR_TO_MINUTE => SQL_IS_HOUR_TO_MINUTE,
+        SQL_C_INTERVAL_HOUR_TO_SECOND => SQL_IS_HOUR_TO_SECOND,
+        SQL_C_INTERVAL_MINUTE_TO_SECOND => SQL_IS_MINUTE_TO_SECOND,
+        _ => SQL_IS_DAY_TO_SECOND,
+    }
+}
+
+/// Build a `SQLGUID` from 16 raw `uniqueidentifier` bytes as read off the
+/// wire, honoring `SQL_ATTR_FURBALL_GUID_BYTE_ORDER`: `Mixed` reads
+/// `data1`/`data2`/`data3` little-endian (the MSDTC/SQL Server on-the-wire
+/// layout, matching the native `SQLGUID` on Windows), `Rfc4122` reads every
+/// field big-endian.
+fn guid_from_bytes(bytes: &[u8; 16], order: GuidByteOrder) -> SqlGuid {
+    let (data1, data2, data3) = match order {
+        GuidByteOrder::Mixed => (
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            u16::from_le_bytes([bytes[4], bytes[5]]),
+            u16::from_le_bytes([bytes[6], bytes[7]]),
+        ),
+        GuidByteOrder::Rfc4122 => (
+            u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            u16::from_be_bytes([bytes[4], bytes[5]]),
+            u16::from_be_bytes([bytes[6], bytes[7]]),
+        ),
+    };
+    SqlGuid {
+        data1,
+        data2,
+        data3,
+        data4: [
+            bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15],
+        ],
+    }
+}
+
 fn parse_guid(s: &str) -> SqlGuid {
     let hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect();
     let bytes = hex_decode(&hex);
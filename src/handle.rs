@@ -1,4 +1,5 @@
 use crate::types::*;
+use std::collections::VecDeque;
 use tabby::RowWriter;
 
 /// Diagnostic record
@@ -8,6 +9,230 @@ pub struct DiagRecord {
     pub message: String,
 }
 
+/// A single cell value, preserving its native type all the way from the
+/// wire to `SQLGetData`/`SQLBindCol` instead of going through a string.
+#[derive(Clone, Debug)]
+pub enum CellValue {
+    Null,
+    Bool(bool),
+    U8(u8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Utf16(Vec<u16>),
+    Bytes(Vec<u8>),
+    Date { days: i32 },
+    Time { nanos: i64 },
+    DateTime { micros: i64 },
+    DateTimeOffset { micros: i64, offset_min: i16 },
+    Decimal { value: i128, precision: u8, scale: u8 },
+    Guid([u8; 16]),
+}
+
+impl CellValue {
+    /// Render the value as text, for C types that ask for a string
+    /// (`SQL_C_CHAR`/`SQL_C_WCHAR`) or as a fallback conversion path.
+    pub fn to_string_repr(&self) -> Option<String> {
+        match self {
+            CellValue::Null => None,
+            CellValue::Bool(v) => Some(if *v { "1" } else { "0" }.to_string()),
+            CellValue::U8(v) => Some(v.to_string()),
+            CellValue::I16(v) => Some(v.to_string()),
+            CellValue::I32(v) => Some(v.to_string()),
+            CellValue::I64(v) => Some(v.to_string()),
+            CellValue::F32(v) => Some(v.to_string()),
+            CellValue::F64(v) => Some(v.to_string()),
+            CellValue::String(s) => Some(s.clone()),
+            CellValue::Utf16(u) => Some(String::from_utf16_lossy(u)),
+            CellValue::Bytes(b) => Some(hex::encode(b)),
+            CellValue::Date { days } => Some(format_date(*days)),
+            CellValue::Time { nanos } => Some(format_time(*nanos)),
+            CellValue::DateTime { micros } => Some(format_datetime(*micros)),
+            CellValue::DateTimeOffset { micros, offset_min } => {
+                let mut s = format_datetime(*micros);
+                let sign = if *offset_min >= 0 { "+" } else { "-" };
+                let abs = offset_min.unsigned_abs();
+                s.push_str(&format!(" {}{:02}:{:02}", sign, abs / 60, abs % 60));
+                Some(s)
+            }
+            CellValue::Decimal {
+                value,
+                precision: _,
+                scale,
+            } => Some(format_decimal(*value, *scale)),
+            CellValue::Guid(bytes) => Some(format_guid(bytes)),
+        }
+    }
+
+    /// Convert a `Decimal` cell to the ODBC `SQL_NUMERIC_STRUCT` binary
+    /// layout requested by `SQL_C_NUMERIC` bindings, preserving the exact
+    /// unscaled integer instead of round-tripping through text.
+    pub fn to_numeric_struct(&self) -> Option<SqlNumericStruct> {
+        match self {
+            CellValue::Decimal {
+                value,
+                precision,
+                scale,
+            } => Some(SqlNumericStruct {
+                precision: *precision,
+                scale: *scale as i8,
+                sign: if *value < 0 { 0 } else { 1 },
+                val: value.unsigned_abs().to_le_bytes(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Approximate in-memory footprint of this cell, for the prefetch
+    /// byte-budget check — close enough to bound buffering on wide/LOB rows
+    /// without tracking a heap allocator's actual overhead.
+    pub fn approx_byte_size(&self) -> usize {
+        let payload = match self {
+            CellValue::String(s) => s.len(),
+            CellValue::Utf16(u) => u.len() * 2,
+            CellValue::Bytes(b) => b.len(),
+            _ => 0,
+        };
+        std::mem::size_of::<CellValue>() + payload
+    }
+}
+
+/// ODBC `SQL_NUMERIC_STRUCT`: a fixed-point decimal as a 16-byte
+/// little-endian unsigned integer plus precision/scale/sign.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct SqlNumericStruct {
+    pub precision: u8,
+    pub scale: i8,
+    pub sign: u8, // 1 = positive, 0 = negative
+    pub val: [u8; 16],
+}
+
+/// SQL Server's `SQL_SS_TIMESTAMPOFFSET_STRUCT`: a `SQL_TIMESTAMP_STRUCT`
+/// extended with a signed hour/minute UTC offset, for `datetimeoffset`
+/// columns bound to their native C type instead of `SQL_C_TYPE_TIMESTAMP`.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct SqlSsTimestampOffsetStruct {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    pub fraction: u32,
+    pub timezone_hour: i16,
+    pub timezone_minute: i16,
+}
+
+/// The year-month half of `SQL_INTERVAL_STRUCT`'s union, for
+/// `SQL_IS_YEAR`/`SQL_IS_MONTH`/`SQL_IS_YEAR_TO_MONTH` intervals.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct SqlYearMonthStruct {
+    pub year: SQLUINTEGER,
+    pub month: SQLUINTEGER,
+}
+
+/// The day-second half of `SQL_INTERVAL_STRUCT`'s union, for every other
+/// `SQL_IS_*` interval (day/hour/minute/second and their TO_ combinations).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct SqlDaySecondStruct {
+    pub day: SQLUINTEGER,
+    pub hour: SQLUINTEGER,
+    pub minute: SQLUINTEGER,
+    pub second: SQLUINTEGER,
+    pub fraction: SQLUINTEGER,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union SqlIntervalValue {
+    pub year_month: SqlYearMonthStruct,
+    pub day_second: SqlDaySecondStruct,
+}
+
+/// `SQL_INTERVAL_STRUCT`: filled for `SQL_C_INTERVAL_*` target types in
+/// `fetch::get_data`, with `intval` populated according to `interval_type`.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct SqlIntervalStruct {
+    pub interval_type: SQLSMALLINT, // one of the SQL_IS_* constants
+    pub interval_sign: SQLSMALLINT, // SQL_FALSE (positive) or SQL_TRUE (negative)
+    pub intval: SqlIntervalValue,
+}
+
+fn format_date(days: i32) -> String {
+    let epoch = 719468i32; // days from 0000-03-01 to 1970-01-01
+    let d = days + epoch;
+    let era = if d >= 0 { d } else { d - 146096 } / 146097;
+    let doe = (d - era * 146097) as u32;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i32 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = if m <= 2 { y + 1 } else { y };
+    format!("{:04}-{:02}-{:02}", year, m, day)
+}
+
+fn format_time(nanos: i64) -> String {
+    let total_secs = (nanos / 1_000_000_000) as u32;
+    let h = total_secs / 3600;
+    let m = (total_secs % 3600) / 60;
+    let s = total_secs % 60;
+    let frac = (nanos % 1_000_000_000) / 1_000_000;
+    format!("{:02}:{:02}:{:02}.{:03}", h, m, s, frac)
+}
+
+fn format_datetime(micros: i64) -> String {
+    let total_secs = micros.div_euclid(1_000_000);
+    let remaining_micros = micros.rem_euclid(1_000_000) as u32;
+    let time_of_day = total_secs.rem_euclid(86400) as u32;
+    let h = time_of_day / 3600;
+    let mi = (time_of_day % 3600) / 60;
+    let sec = time_of_day % 60;
+    let millis = remaining_micros / 1000;
+    let days = total_secs.div_euclid(86400) as i32;
+    let date = format_date(days);
+    format!("{} {:02}:{:02}:{:02}.{:03}", date, h, mi, sec, millis)
+}
+
+fn format_decimal(value: i128, scale: u8) -> String {
+    let negative = value < 0;
+    let abs = value.unsigned_abs();
+    let s = abs.to_string();
+    let scale = scale as usize;
+    let result = if scale == 0 {
+        s
+    } else if s.len() <= scale {
+        format!("0.{}{}", "0".repeat(scale - s.len()), s)
+    } else {
+        let (int_part, frac_part) = s.split_at(s.len() - scale);
+        format!("{}.{}", int_part, frac_part)
+    };
+    if negative {
+        format!("-{}", result)
+    } else {
+        result
+    }
+}
+
+fn format_guid(bytes: &[u8; 16]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_be_bytes([bytes[4], bytes[5]]),
+        u16::from_be_bytes([bytes[6], bytes[7]]),
+        bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]
+    )
+}
+
 /// Column descriptor
 pub struct ColumnDesc {
     pub name: String,
@@ -15,12 +240,63 @@ pub struct ColumnDesc {
     pub size: SQLULEN,
     pub decimal_digits: SQLSMALLINT,
     pub nullable: SQLSMALLINT,
+    /// SQL Server-specific type code (e.g. `SQL_SS_XML`) to report instead of
+    /// `sql_type` when a caller asks for the column's native data type; `None`
+    /// for ordinary columns that don't have a vendor-specific code.
+    pub ss_type: Option<SQLSMALLINT>,
+    /// Source-table provenance for `SQL_DESC_BASE_TABLE_NAME`/
+    /// `SQL_DESC_BASE_COLUMN_NAME`/`SQL_DESC_SCHEMA_NAME`/
+    /// `SQL_DESC_CATALOG_NAME`; empty for computed/expression columns the
+    /// server can't trace back to a single base table.
+    pub base_table: String,
+    pub base_column: String,
+    pub schema: String,
+    pub catalog: String,
+}
+
+/// Event delivered to a connection's registered trace callback (see
+/// `Connection::trace_callback`), modeled on rusqlite's `trace` feature.
+pub enum TraceEvent<'a> {
+    /// `conn_str` is the resolved connection string with `UID=`/`PWD=` values
+    /// redacted — see `connect::redact_conn_str`.
+    Connect {
+        server: &'a str,
+        database: &'a str,
+        conn_str: &'a str,
+    },
+    Disconnect { server: &'a str, database: &'a str },
+    Query {
+        server: &'a str,
+        database: &'a str,
+        /// Monotonically increasing per-statement id (see
+        /// `Statement::trace_id`), so a log can tell repeated executions of
+        /// the same statement apart from unrelated ones.
+        stmt_id: u64,
+        sql: &'a str,
+        elapsed: std::time::Duration,
+        /// Rows affected/returned on success (-1 when not meaningful, e.g. a
+        /// result-set-producing query that streams rather than counting up
+        /// front), or the error text on failure.
+        result: Result<SQLLEN, &'a str>,
+    },
+    /// `BEGIN TRANSACTION`/`COMMIT`/`ROLLBACK`, fired from `exec_direct`'s
+    /// autocommit-off transaction start and from `SQLEndTran`.
+    Transaction {
+        server: &'a str,
+        database: &'a str,
+        kind: &'static str,
+    },
 }
 
+/// Callback registered via the driver-specific `SQL_ATTR_FURBALL_TRACE_CALLBACK`
+/// connection attribute; see `set_connect_attr`.
+pub type TraceCallback = Box<dyn Fn(&TraceEvent) + Send + Sync>;
+
 /// Environment handle
 pub struct Environment {
     pub odbc_version: SQLINTEGER,
     pub connections: Vec<*mut Connection>,
+    pub diagnostics: Vec<DiagRecord>,
 }
 
 /// Connection handle
@@ -36,14 +312,42 @@ pub struct Connection {
     pub connected: bool,
     pub autocommit: bool,
     pub in_transaction: bool,
+    pub login_timeout_secs: u32,      // SQL_ATTR_LOGIN_TIMEOUT; 0 = no timeout
+    pub connection_timeout_secs: u32, // SQL_ATTR_CONNECTION_TIMEOUT; 0 = no timeout
+    pub isolation_level: SQLINTEGER,  // SQL_ATTR_TXN_ISOLATION, re-applied before each BEGIN TRANSACTION
+    pub read_only: bool,              // SQL_ATTR_ACCESS_MODE == SQL_MODE_READ_ONLY
+    // SQL_ATTR_FURBALL_TRACE_CALLBACK; fired around connect/disconnect and
+    // every exec_direct call when set, a null check otherwise.
+    pub trace_callback: Option<TraceCallback>,
+    // SQL_ATTR_FURBALL_DATETIMEOFFSET_LOCAL; off by default so existing
+    // callers keep seeing DateTimeOffset values reported in UTC.
+    pub normalize_timestampoffset_local: bool,
+    // SQL_ATTR_FURBALL_GUID_BYTE_ORDER; defaults to Mixed to match SQL_GUID /
+    // SQL_C_GUID on Windows.
+    pub guid_byte_order: GuidByteOrder,
+    // SQL_ATTR_FURBALL_CHARSET; encoding diagnostic message text is
+    // transcoded to before being returned from SQLGetDiagRec (SQLGetDiagField
+    // returns message text as UTF-8).
+    pub charset: String,
+}
+
+/// How `data1`/`data2`/`data3` of a `uniqueidentifier` are laid out in bytes
+/// read off the wire, selected by `SQL_ATTR_FURBALL_GUID_BYTE_ORDER`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuidByteOrder {
+    /// RFC 4122: all four fields big-endian.
+    Rfc4122,
+    /// SQL Server / MSDTC on-the-wire layout: `data1`/`data2`/`data3`
+    /// little-endian, `data4` big-endian — matches the native `SQLGUID`.
+    Mixed,
 }
 
 /// Statement handle  
 pub struct Statement {
     pub conn: *mut Connection,
     pub columns: Vec<ColumnDesc>,
-    pub rows: Vec<Vec<Option<String>>>, // all results in memory as strings
-    pub row_index: isize,               // -1 = before first row
+    pub rows: Vec<Vec<CellValue>>, // all results in memory, one native-typed cell each
+    pub row_index: isize,          // -1 = before first row
     pub diagnostics: Vec<DiagRecord>,
     pub executed: bool,
     pub prepared_sql: Option<String>,
@@ -51,6 +355,11 @@ pub struct Statement {
     pub bound_params: Vec<BoundParam>,
     pub read_offsets: Vec<usize>, // tracks how much of each column has been read (for chunked SQLGetData)
     pub paramset_size: usize,     // SQL_ATTR_PARAMSET_SIZE, default 1
+    pub param_status_ptr: *mut SQLUSMALLINT, // SQL_ATTR_PARAM_STATUS_PTR
+    pub params_processed_ptr: *mut SQLULEN, // SQL_ATTR_PARAMS_PROCESSED_PTR
+    // Assigned once from `trace::next_stmt_id()` at allocation time so traced
+    // `TraceEvent::Query` events can be tied back to the statement.
+    pub trace_id: u64,
     // DAE (data-at-execution) state
     pub dae_sql: Option<String>, // SQL to execute once all DAE params are collected
     pub dae_params_needed: Vec<u16>, // param numbers that need DAE data (in order)
@@ -59,6 +368,55 @@ pub struct Statement {
     pub dae_current_buf: Vec<u8>, // buffer for current param being collected via SQLPutData
     // Multiple result sets
     pub pending_result_sets: Vec<ResultSet>, // remaining result sets after the current one
+    // Streaming forward-only cursor (the default — see `cursor_type`)
+    pub cursor_type: SQLINTEGER, // SQL_ATTR_CURSOR_TYPE; SQL_CURSOR_FORWARD_ONLY streams, others buffer
+    pub streaming: bool,         // true while rows are being pulled on demand rather than buffered
+    pub current_row: Vec<CellValue>, // scratch space for the row currently being decoded
+    pub prefetch_buffer: VecDeque<Vec<CellValue>>, // rows decoded ahead of what the app has fetched
+    pub prefetch_done: Option<PrefetchTerminal>,    // set once the prefetch loop hits a terminal state
+    pub stream_string_buf: String, // reused scratch buffer for decoding variable-length text
+    pub stream_bytes_buf: Vec<u8>, // reused scratch buffer for decoding variable-length binary
+    pub row_array_size: SQLULEN, // SQL_ATTR_ROW_ARRAY_SIZE; rows per block fetch, default 1
+    pub query_timeout_secs: u32, // SQL_ATTR_QUERY_TIMEOUT; 0 = no timeout
+    // Signaled by `SQLCancel` (possibly from another thread) to interrupt the
+    // in-flight `block_on` future in `exec_direct`; recreated at the start of
+    // each execution so a stale cancel can't leak into the next statement.
+    pub cancel_token: std::sync::Arc<tokio::sync::Notify>,
+    pub max_rows: SQLULEN,      // SQL_ATTR_MAX_ROWS; 0 = unlimited
+    pub concurrency: SQLINTEGER, // SQL_ATTR_CONCURRENCY; only SQL_CONCUR_READ_ONLY is honored
+    pub rows_fetched: u64,      // rows handed back so far this execution, for enforcing max_rows
+    // Block-cursor column bindings from SQLBindCol; when non-empty, `fetch`
+    // drains up to `row_array_size` rows per call into the bound buffers
+    // instead of handing back a single row for the app to pull via SQLGetData.
+    pub bound_cols: Vec<BoundColumn>,
+    pub row_bind_type: SQLULEN, // SQL_ATTR_ROW_BIND_TYPE; SQL_BIND_BY_COLUMN (0) or a row struct stride
+    pub row_status_ptr: *mut SQLUSMALLINT, // SQL_ATTR_ROW_STATUS_PTR
+    pub rows_fetched_ptr: *mut SQLULEN,    // SQL_ATTR_ROWS_FETCHED_PTR
+    // SQL_ATTR_FURBALL_PREFETCH_ROWS; rows pulled ahead of the app per
+    // prefetch round (a floor of row_array_size is always applied on top).
+    pub prefetch_rows: SQLULEN,
+    // Approximate cumulative CellValue bytes a prefetch round stops at, even
+    // if prefetch_rows hasn't been reached yet; bounds memory use on
+    // LOB-bearing result sets. Not attribute-configurable, just a sane cap.
+    pub prefetch_byte_budget: usize,
+}
+
+/// A column binding recorded by `SQLBindCol`, consumed by block fetches once
+/// one or more columns are bound (see `Statement::bound_cols`).
+#[derive(Clone, Copy)]
+pub struct BoundColumn {
+    pub col_number: SQLUSMALLINT,
+    pub target_type: SQLSMALLINT,
+    pub target_value: SQLPOINTER,
+    pub buffer_length: SQLLEN,
+    pub str_len_or_ind: *mut SQLLEN,
+}
+
+/// Terminal state reached while prefetching rows for a streaming cursor.
+pub enum PrefetchTerminal {
+    Done,
+    MoreResults,
+    Error(String),
 }
 
 /// A bound parameter
@@ -77,16 +435,39 @@ pub struct BoundParam {
 /// A single result set (columns + rows)
 pub struct ResultSet {
     pub columns: Vec<ColumnDesc>,
-    pub rows: Vec<Vec<Option<String>>>,
+    /// Column-major: `columns_data[col][row]`. It is transposed to row-major
+    /// by `into_rows` before any row is fetched; block fetches do not copy
+    /// column slices from it.
+    pub columns_data: Vec<Vec<CellValue>>,
     pub done_rows: u64,
 }
 
-// RowWriter implementation that collects everything as strings
+impl ResultSet {
+    /// Transpose the column-major buffer into row-major order, the layout
+    /// `Statement::rows` holds for every buffered (e.g. scrollable-cursor)
+    /// result set.
+    pub fn into_rows(&mut self) -> Vec<Vec<CellValue>> {
+        let columns_data = std::mem::take(&mut self.columns_data);
+        let num_rows = columns_data.first().map(|c| c.len()).unwrap_or(0);
+        let mut rows: Vec<Vec<CellValue>> = (0..num_rows)
+            .map(|_| Vec::with_capacity(columns_data.len()))
+            .collect();
+        for col in columns_data {
+            for (row_idx, cell) in col.into_iter().enumerate() {
+                rows[row_idx].push(cell);
+            }
+        }
+        rows
+    }
+}
+
+// RowWriter implementation that collects each value as a native-typed CellValue,
+// buffered column-major (one contiguous Vec<CellValue> per column) until
+// `ResultSet::into_rows` turns it into rows.
 pub struct StringRowWriter {
     pub result_sets: Vec<ResultSet>,
     current_columns: Vec<ColumnDesc>,
-    current_rows: Vec<Vec<Option<String>>>,
-    current_row: Vec<Option<String>>,
+    current_columns_data: Vec<Vec<CellValue>>, // outer index = column, inner = row
     got_metadata: bool,
     pub done_rows: u64,
     pub info_messages: Vec<(u32, String)>,
@@ -97,8 +478,7 @@ impl StringRowWriter {
         Self {
             result_sets: Vec::new(),
             current_columns: Vec::new(),
-            current_rows: Vec::new(),
-            current_row: Vec::new(),
+            current_columns_data: Vec::new(),
             got_metadata: false,
             done_rows: 0,
             info_messages: Vec::new(),
@@ -110,7 +490,7 @@ impl StringRowWriter {
         if self.got_metadata {
             self.result_sets.push(ResultSet {
                 columns: std::mem::take(&mut self.current_columns),
-                rows: std::mem::take(&mut self.current_rows),
+                columns_data: std::mem::take(&mut self.current_columns_data),
                 done_rows: self.done_rows,
             });
             self.got_metadata = false;
@@ -119,7 +499,9 @@ impl StringRowWriter {
     }
 }
 
-fn sql_type_from_column(c: &tabby::Column) -> (SQLSMALLINT, SQLULEN, SQLSMALLINT, SQLSMALLINT) {
+pub(crate) fn sql_type_from_column(
+    c: &tabby::Column,
+) -> (SQLSMALLINT, SQLULEN, SQLSMALLINT, SQLSMALLINT, Option<SQLSMALLINT>) {
     let type_name = format!("{:?}", c.column_type());
     let sql_type = match type_name.as_str() {
         "Int4" => SQL_INTEGER,
@@ -135,6 +517,7 @@ fn sql_type_from_column(c: &tabby::Column) -> (SQLSMALLINT, SQLULEN, SQLSMALLINT
         "NChar" => SQL_WCHAR,
         "Text" => SQL_LONGVARCHAR,
         "NText" => SQL_WLONGVARCHAR,
+        "Xml" => SQL_WLONGVARCHAR,
         "BigBinary" => SQL_BINARY,
         "BigVarBin" => SQL_VARBINARY,
         "Image" => SQL_LONGVARBINARY,
@@ -145,6 +528,13 @@ fn sql_type_from_column(c: &tabby::Column) -> (SQLSMALLINT, SQLULEN, SQLSMALLINT
         "Guid" => SQL_GUID,
         _ => SQL_VARCHAR,
     };
+    // SQL Server's own extended type code, for clients that special-case XML
+    // (e.g. to request the document as UTF-16 text) instead of treating it
+    // as a plain long varchar.
+    let ss_type = match type_name.as_str() {
+        "Xml" => Some(SQL_SS_XML),
+        _ => None,
+    };
     let nullable = if c.nullable().unwrap_or(true) {
         SQL_NULLABLE
     } else {
@@ -215,7 +605,21 @@ fn sql_type_from_column(c: &tabby::Column) -> (SQLSMALLINT, SQLULEN, SQLSMALLINT
         _ => 256,
     };
 
-    (sql_type, size, decimal_digits, nullable)
+    (sql_type, size, decimal_digits, nullable, ss_type)
+}
+
+/// Source-table provenance for a result column, for `SQL_DESC_BASE_TABLE_NAME`
+/// / `SQL_DESC_BASE_COLUMN_NAME` / `SQL_DESC_SCHEMA_NAME` /
+/// `SQL_DESC_CATALOG_NAME`. The server only reports this for columns it can
+/// trace back to a single base table; computed/expression columns (and
+/// anything the wire protocol doesn't tag) fall back to empty strings.
+pub(crate) fn column_provenance(c: &tabby::Column) -> (String, String, String, String) {
+    (
+        c.base_table_name().unwrap_or_default().to_string(),
+        c.base_column_name().unwrap_or_default().to_string(),
+        c.schema_name().unwrap_or_default().to_string(),
+        c.catalog_name().unwrap_or_default().to_string(),
+    )
 }
 
 impl RowWriter for StringRowWriter {
@@ -224,7 +628,7 @@ impl RowWriter for StringRowWriter {
         if self.got_metadata {
             self.result_sets.push(ResultSet {
                 columns: std::mem::take(&mut self.current_columns),
-                rows: std::mem::take(&mut self.current_rows),
+                columns_data: std::mem::take(&mut self.current_columns_data),
                 done_rows: self.done_rows,
             });
             self.done_rows = 0;
@@ -233,26 +637,28 @@ impl RowWriter for StringRowWriter {
         self.current_columns = columns
             .iter()
             .map(|c| {
-                let (sql_type, size, decimal_digits, nullable) = sql_type_from_column(c);
+                let (sql_type, size, decimal_digits, nullable, ss_type) = sql_type_from_column(c);
+                let (base_table, base_column, schema, catalog) = column_provenance(c);
                 ColumnDesc {
                     name: c.name().to_string(),
                     sql_type,
                     size,
                     decimal_digits,
                     nullable,
+                    ss_type,
+                    base_table,
+                    base_column,
+                    schema,
+                    catalog,
                 }
             })
             .collect();
+        self.current_columns_data = (0..self.current_columns.len()).map(|_| Vec::new()).collect();
     }
 
     fn on_row_done(&mut self) {
-        if self.got_metadata {
-            let row = std::mem::replace(
-                &mut self.current_row,
-                Vec::with_capacity(self.current_columns.len()),
-            );
-            self.current_rows.push(row);
-        }
+        // Each write_* call already appended straight into the owning
+        // column's buffer, so there's nothing left to move on row completion.
     }
 
     fn on_done(&mut self, rows: u64) {
@@ -263,132 +669,60 @@ impl RowWriter for StringRowWriter {
         self.info_messages.push((number, message.to_string()));
     }
 
-    fn write_null(&mut self, _col: usize) {
-        self.current_row.push(None);
+    fn write_null(&mut self, col: usize) {
+        self.current_columns_data[col].push(CellValue::Null);
     }
-    fn write_bool(&mut self, _col: usize, val: bool) {
-        self.current_row
-            .push(Some(if val { "1" } else { "0" }.to_string()));
+    fn write_bool(&mut self, col: usize, val: bool) {
+        self.current_columns_data[col].push(CellValue::Bool(val));
     }
-    fn write_u8(&mut self, _col: usize, val: u8) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_u8(&mut self, col: usize, val: u8) {
+        self.current_columns_data[col].push(CellValue::U8(val));
     }
-    fn write_i16(&mut self, _col: usize, val: i16) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_i16(&mut self, col: usize, val: i16) {
+        self.current_columns_data[col].push(CellValue::I16(val));
     }
-    fn write_i32(&mut self, _col: usize, val: i32) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_i32(&mut self, col: usize, val: i32) {
+        self.current_columns_data[col].push(CellValue::I32(val));
     }
-    fn write_i64(&mut self, _col: usize, val: i64) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_i64(&mut self, col: usize, val: i64) {
+        self.current_columns_data[col].push(CellValue::I64(val));
     }
-    fn write_f32(&mut self, _col: usize, val: f32) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_f32(&mut self, col: usize, val: f32) {
+        self.current_columns_data[col].push(CellValue::F32(val));
     }
-    fn write_f64(&mut self, _col: usize, val: f64) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_f64(&mut self, col: usize, val: f64) {
+        self.current_columns_data[col].push(CellValue::F64(val));
     }
-    fn write_str(&mut self, _col: usize, val: &str) {
-        self.current_row.push(Some(val.to_string()));
+    fn write_str(&mut self, col: usize, val: &str) {
+        self.current_columns_data[col].push(CellValue::String(val.to_string()));
     }
-    fn write_bytes(&mut self, _col: usize, val: &[u8]) {
-        self.current_row.push(Some(hex::encode(val)));
+    fn write_bytes(&mut self, col: usize, val: &[u8]) {
+        self.current_columns_data[col].push(CellValue::Bytes(val.to_vec()));
     }
-    fn write_date(&mut self, _col: usize, days: i32) {
-        // days since unix epoch
-        let epoch = 719468i32; // days from 0000-03-01 to 1970-01-01
-        let d = days + epoch;
-        let era = if d >= 0 { d } else { d - 146096 } / 146097;
-        let doe = (d - era * 146097) as u32;
-        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-        let y = yoe as i32 + era * 400;
-        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-        let mp = (5 * doy + 2) / 153;
-        let day = doy - (153 * mp + 2) / 5 + 1;
-        let m = if mp < 10 { mp + 3 } else { mp - 9 };
-        let year = if m <= 2 { y + 1 } else { y };
-        self.current_row
-            .push(Some(format!("{:04}-{:02}-{:02}", year, m, day)));
+    fn write_date(&mut self, col: usize, days: i32) {
+        self.current_columns_data[col].push(CellValue::Date { days });
     }
-    fn write_time(&mut self, _col: usize, nanos: i64) {
-        let total_secs = (nanos / 1_000_000_000) as u32;
-        let h = total_secs / 3600;
-        let m = (total_secs % 3600) / 60;
-        let s = total_secs % 60;
-        let frac = (nanos % 1_000_000_000) / 1_000_000;
-        self.current_row
-            .push(Some(format!("{:02}:{:02}:{:02}.{:03}", h, m, s, frac)));
+    fn write_time(&mut self, col: usize, nanos: i64) {
+        self.current_columns_data[col].push(CellValue::Time { nanos });
     }
-    fn write_datetime(&mut self, _col: usize, micros: i64) {
-        let total_secs = micros.div_euclid(1_000_000);
-        let remaining_micros = micros.rem_euclid(1_000_000) as u32;
-        let time_of_day = total_secs.rem_euclid(86400) as u32;
-        let h = time_of_day / 3600;
-        let mi = (time_of_day % 3600) / 60;
-        let sec = time_of_day % 60;
-        let millis = remaining_micros / 1000;
-        let mut days = total_secs.div_euclid(86400) as i32;
-        days += 719468;
-        let era = if days >= 0 { days } else { days - 146096 } / 146097;
-        let doe = (days - era * 146097) as u32;
-        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-        let y = yoe as i32 + era * 400;
-        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-        let mp = (5 * doy + 2) / 153;
-        let d = doy - (153 * mp + 2) / 5 + 1;
-        let m = if mp < 10 { mp + 3 } else { mp - 9 };
-        let year = if m <= 2 { y + 1 } else { y };
-        self.current_row.push(Some(format!(
-            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
-            year, m, d, h, mi, sec, millis
-        )));
+    fn write_datetime(&mut self, col: usize, micros: i64) {
+        self.current_columns_data[col].push(CellValue::DateTime { micros });
     }
-    fn write_datetimeoffset(&mut self, _col: usize, micros: i64, offset_minutes: i16) {
-        // Just write as datetime for now
-        self.write_datetime(_col, micros);
-        // Append offset
-        if let Some(Some(s)) = self.current_row.last_mut() {
-            let sign = if offset_minutes >= 0 { "+" } else { "-" };
-            let abs = offset_minutes.unsigned_abs();
-            s.push_str(&format!(" {}{:02}:{:02}", sign, abs / 60, abs % 60));
-        }
+    fn write_datetimeoffset(&mut self, col: usize, micros: i64, offset_minutes: i16) {
+        self.current_columns_data[col].push(CellValue::DateTimeOffset {
+            micros,
+            offset_min: offset_minutes,
+        });
     }
-    fn write_decimal(&mut self, _col: usize, value: i128, _precision: u8, scale: u8) {
-        let negative = value < 0;
-        let abs = value.unsigned_abs();
-        let s = abs.to_string();
-        let scale = scale as usize;
-        let result = if scale == 0 {
-            s
-        } else if s.len() <= scale {
-            format!("0.{}{}", "0".repeat(scale - s.len()), s)
-        } else {
-            let (int_part, frac_part) = s.split_at(s.len() - scale);
-            format!("{}.{}", int_part, frac_part)
-        };
-        let result = if negative {
-            format!("-{}", result)
-        } else {
-            result
-        };
-        self.current_row.push(Some(result));
+    fn write_decimal(&mut self, col: usize, value: i128, precision: u8, scale: u8) {
+        self.current_columns_data[col].push(CellValue::Decimal {
+            value,
+            precision,
+            scale,
+        });
     }
-    fn write_guid(&mut self, _col: usize, bytes: &[u8; 16]) {
-        let fmt = format!(
-            "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
-            u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
-            u16::from_be_bytes([bytes[4], bytes[5]]),
-            u16::from_be_bytes([bytes[6], bytes[7]]),
-            bytes[8],
-            bytes[9],
-            bytes[10],
-            bytes[11],
-            bytes[12],
-            bytes[13],
-            bytes[14],
-            bytes[15]
-        );
-        self.current_row.push(Some(fmt));
+    fn write_guid(&mut self, col: usize, bytes: &[u8; 16]) {
+        self.current_columns_data[col].push(CellValue::Guid(*bytes));
     }
 }
 
@@ -1,12 +1,14 @@
 #![allow(non_snake_case)]
 
 mod attr;
+mod catalog;
 mod connect;
 mod diagnostics;
 mod execute;
 mod fetch;
 mod handle;
 mod runtime;
+mod trace;
 mod types;
 
 use handle::*;
@@ -14,6 +16,9 @@ use std::ffi::CStr;
 use std::ptr;
 use types::*;
 
+pub use handle::TraceEvent;
+pub use trace::furball_set_trace_callback;
+
 // ── Helper: extract string from SQLCHAR* + length ───────────────────
 
 fn wchar_to_string(ptr: *const SQLWCHAR, len: SQLSMALLINT) -> String {
@@ -83,6 +88,7 @@ fn alloc_handle_impl(
             let env = Box::new(Environment {
                 odbc_version: SQL_OV_ODBC3,
                 connections: Vec::new(),
+                diagnostics: Vec::new(),
             });
             unsafe {
                 *output_handle = Box::into_raw(env) as SQLHANDLE;
@@ -106,6 +112,14 @@ fn alloc_handle_impl(
                 connected: false,
                 autocommit: true,
                 in_transaction: false,
+                login_timeout_secs: 0,
+                connection_timeout_secs: 0,
+                isolation_level: SQL_TXN_READ_COMMITTED,
+                read_only: false,
+                trace_callback: None,
+                normalize_timestampoffset_local: false,
+                guid_byte_order: GuidByteOrder::Mixed,
+                charset: "UTF-8".to_string(),
             });
             let conn_ptr = Box::into_raw(conn);
             if !input_handle.is_null() {
@@ -132,6 +146,36 @@ fn alloc_handle_impl(
                 prepared_sql: None,
                 row_count: -1,
                 bound_params: Vec::new(),
+                read_offsets: Vec::new(),
+                paramset_size: 1,
+                param_status_ptr: std::ptr::null_mut(),
+                params_processed_ptr: std::ptr::null_mut(),
+                trace_id: crate::trace::next_stmt_id(),
+                dae_sql: None,
+                dae_params_needed: Vec::new(),
+                dae_current_idx: 0,
+                dae_collected: Vec::new(),
+                dae_current_buf: Vec::new(),
+                pending_result_sets: Vec::new(),
+                cursor_type: SQL_CURSOR_FORWARD_ONLY,
+                streaming: false,
+                current_row: Vec::new(),
+                prefetch_buffer: std::collections::VecDeque::new(),
+                prefetch_done: None,
+                stream_string_buf: String::new(),
+                stream_bytes_buf: Vec::new(),
+                row_array_size: 1,
+                query_timeout_secs: 0,
+                cancel_token: std::sync::Arc::new(tokio::sync::Notify::new()),
+                max_rows: 0,
+                concurrency: SQL_CONCUR_READ_ONLY,
+                rows_fetched: 0,
+                bound_cols: Vec::new(),
+                row_bind_type: SQL_BIND_BY_COLUMN as SQLULEN,
+                row_status_ptr: std::ptr::null_mut(),
+                rows_fetched_ptr: std::ptr::null_mut(),
+                prefetch_rows: 256,
+                prefetch_byte_budget: 4 * 1024 * 1024,
             });
             let stmt_ptr = Box::into_raw(stmt);
             if !input_handle.is_null() {
@@ -284,7 +328,7 @@ pub extern "C" fn SQLExecDirect(
     stmt.diagnostics.clear();
 
     let sql = unsafe { sql_str(statement_text, text_length as SQLSMALLINT) };
-    execute::exec_direct(stmt, &sql)
+    exec_with_bound_params(stmt, &sql)
 }
 
 #[unsafe(no_mangle)]
@@ -318,7 +362,7 @@ pub extern "C" fn SQLExecDirectW(
         String::from_utf16_lossy(slice)
     };
 
-    execute::exec_direct(stmt, &sql)
+    exec_with_bound_params(stmt, &sql)
 }
 
 // ── Results ─────────────────────────────────────────────────────────
@@ -412,8 +456,16 @@ pub extern "C" fn SQLGetDataW(
     }
     let stmt = unsafe { &*(hstmt as *const Statement) };
 
-    // For non-character target types, delegate to ANSI version
-    if target_type != SQL_C_WCHAR && target_type != SQL_C_DEFAULT && target_type != SQL_C_CHAR {
+    // For non-character target types, delegate to ANSI version. A
+    // SQL_C_DEFAULT request also delegates when the column's natural type
+    // isn't character data (e.g. an int column should come back as
+    // SQL_C_LONG, not a wide string), matching `fetch::default_c_type`.
+    let col_idx = (col as usize).wrapping_sub(1);
+    let is_char_request = target_type == SQL_C_WCHAR
+        || target_type == SQL_C_CHAR
+        || (target_type == SQL_C_DEFAULT
+            && fetch::default_c_type(stmt, col_idx) == SQL_C_CHAR);
+    if !is_char_request {
         return fetch::get_data(
             stmt,
             col,
@@ -429,13 +481,12 @@ pub extern "C" fn SQLGetDataW(
         return SQL_ERROR;
     }
     let row = &stmt.rows[stmt.row_index as usize];
-    let col_idx = (col as usize).wrapping_sub(1);
     if col_idx >= row.len() {
         return SQL_ERROR;
     }
 
     match &row[col_idx] {
-        None => {
+        CellValue::Null => {
             if !str_len_or_ind.is_null() {
                 unsafe {
                     *str_len_or_ind = SQL_NULL_DATA;
@@ -443,7 +494,8 @@ pub extern "C" fn SQLGetDataW(
             }
             SQL_SUCCESS
         }
-        Some(val) => {
+        cell => {
+            let val = cell.to_string_repr().unwrap_or_default();
             let utf16: Vec<u16> = val.encode_utf16().collect();
             let data_len_bytes = (utf16.len() * 2) as SQLLEN;
 
@@ -506,61 +558,16 @@ pub extern "C" fn SQLGetDiagRecW(
     buffer_length: SQLSMALLINT,
     text_length: *mut SQLSMALLINT,
 ) -> SQLRETURN {
-    // Get the ANSI version first, then convert
-    let mut state_buf = [0u8; 6];
-    let mut native = 0i32;
-    let mut msg_buf = [0u8; 4096];
-    let mut msg_len: SQLSMALLINT = 0;
-
-    let ret = diagnostics::get_diag_rec(
+    diagnostics::get_diag_rec_w(
         handle_type,
         handle,
         rec_number,
-        state_buf.as_mut_ptr(),
-        &mut native,
-        msg_buf.as_mut_ptr(),
-        4096,
-        &mut msg_len,
-    );
-
-    if ret != SQL_SUCCESS && ret != SQL_SUCCESS_WITH_INFO {
-        return ret;
-    }
-
-    // Copy SQLSTATE as wide chars
-    if !sql_state.is_null() {
-        for i in 0..6 {
-            unsafe {
-                *sql_state.add(i) = state_buf[i] as u16;
-            }
-        }
-    }
-    if !native_error.is_null() {
-        unsafe {
-            *native_error = native;
-        }
-    }
-
-    // Copy message as wide chars
-    let msg_len_usize = msg_len as usize;
-    if !message_text.is_null() && buffer_length > 0 {
-        let copy_len = std::cmp::min(msg_len_usize, (buffer_length as usize).saturating_sub(1));
-        for i in 0..copy_len {
-            unsafe {
-                *message_text.add(i) = msg_buf[i] as u16;
-            }
-        }
-        unsafe {
-            *message_text.add(copy_len) = 0;
-        }
-    }
-    if !text_length.is_null() {
-        unsafe {
-            *text_length = msg_len;
-        }
-    }
-
-    ret
+        sql_state,
+        native_error,
+        message_text,
+        buffer_length,
+        text_length,
+    )
 }
 
 // ── Attributes ──────────────────────────────────────────────────────
@@ -625,7 +632,6 @@ pub extern "C" fn SQLGetConnectAttr(
         return SQL_INVALID_HANDLE;
     }
     let conn = unsafe { &*(hdbc as *mut Connection) };
-    let _ = (buffer_length, string_length);
     match attribute {
         SQL_ATTR_AUTOCOMMIT => {
             if !value.is_null() {
@@ -635,6 +641,30 @@ pub extern "C" fn SQLGetConnectAttr(
             }
             SQL_SUCCESS
         }
+        SQL_ATTR_TXN_ISOLATION => {
+            if !value.is_null() {
+                unsafe {
+                    *(value as *mut SQLINTEGER) = conn.isolation_level;
+                }
+            }
+            SQL_SUCCESS
+        }
+        attr::SQL_ATTR_FURBALL_CHARSET => {
+            let bytes = conn.charset.as_bytes();
+            if !string_length.is_null() {
+                unsafe {
+                    *string_length = bytes.len() as SQLINTEGER;
+                }
+            }
+            if !value.is_null() && buffer_length > 0 {
+                let copy_len = std::cmp::min(bytes.len(), (buffer_length as usize).saturating_sub(1));
+                unsafe {
+                    ptr::copy_nonoverlapping(bytes.as_ptr(), value as *mut u8, copy_len);
+                    *((value as *mut u8).add(copy_len)) = 0;
+                }
+            }
+            SQL_SUCCESS
+        }
         _ => SQL_SUCCESS,
     }
 }
@@ -647,8 +677,7 @@ pub extern "C" fn SQLGetConnectAttrW(
     buffer_length: SQLINTEGER,
     string_length: *mut SQLINTEGER,
 ) -> SQLRETURN {
-    let _ = (attribute, value, buffer_length, string_length);
-    SQL_SUCCESS
+    SQLGetConnectAttr(hdbc, attribute, value, buffer_length, string_length)
 }
 
 #[unsafe(no_mangle)]
@@ -803,7 +832,7 @@ pub extern "C" fn SQLColAttribute(
             write_str_attr(&col.name)
         }
         SQL_DESC_CONCISE_TYPE | SQL_DESC_TYPE | SQL_COLUMN_TYPE => {
-            write_num(col.sql_type as SQLLEN)
+            write_num(col.ss_type.unwrap_or(col.sql_type) as SQLLEN)
         }
         SQL_DESC_LENGTH | SQL_COLUMN_LENGTH => write_num(col.size as SQLLEN),
         SQL_DESC_DISPLAY_SIZE | SQL_COLUMN_DISPLAY_SIZE => {
@@ -837,28 +866,34 @@ pub extern "C" fn SQLColAttribute(
         SQL_DESC_SEARCHABLE => write_num(3), // SQL_SEARCHABLE
         SQL_DESC_UNSIGNED => write_num(0),
         SQL_DESC_UPDATABLE => write_num(0), // SQL_ATTR_READONLY
-        SQL_DESC_TABLE_NAME => write_str_attr(""),
+        SQL_DESC_TABLE_NAME | SQL_DESC_BASE_TABLE_NAME => write_str_attr(&col.base_table),
+        SQL_DESC_BASE_COLUMN_NAME => write_str_attr(&col.base_column),
+        SQL_DESC_SCHEMA_NAME => write_str_attr(&col.schema),
+        SQL_DESC_CATALOG_NAME => write_str_attr(&col.catalog),
         SQL_DESC_TYPE_NAME => {
-            let type_name = match col.sql_type {
-                SQL_INTEGER => "int",
-                SQL_SMALLINT => "smallint",
-                SQL_TINYINT => "tinyint",
-                SQL_BIGINT => "bigint",
-                SQL_BIT => "bit",
-                SQL_DOUBLE | SQL_FLOAT => "float",
-                SQL_REAL => "real",
-                SQL_VARCHAR => "varchar",
-                SQL_CHAR => "char",
-                SQL_WVARCHAR => "nvarchar",
-                SQL_WCHAR => "nchar",
-                SQL_TYPE_TIMESTAMP => "datetime",
-                SQL_TYPE_DATE => "date",
-                SQL_TYPE_TIME => "time",
-                SQL_DECIMAL | SQL_NUMERIC => "decimal",
-                SQL_BINARY => "binary",
-                SQL_VARBINARY => "varbinary",
-                SQL_GUID => "uniqueidentifier",
-                _ => "varchar",
+            let type_name = match col.ss_type {
+                Some(SQL_SS_XML) => "xml",
+                _ => match col.sql_type {
+                    SQL_INTEGER => "int",
+                    SQL_SMALLINT => "smallint",
+                    SQL_TINYINT => "tinyint",
+                    SQL_BIGINT => "bigint",
+                    SQL_BIT => "bit",
+                    SQL_DOUBLE | SQL_FLOAT => "float",
+                    SQL_REAL => "real",
+                    SQL_VARCHAR => "varchar",
+                    SQL_CHAR => "char",
+                    SQL_WVARCHAR => "nvarchar",
+                    SQL_WCHAR => "nchar",
+                    SQL_TYPE_TIMESTAMP => "datetime",
+                    SQL_TYPE_DATE => "date",
+                    SQL_TYPE_TIME => "time",
+                    SQL_DECIMAL | SQL_NUMERIC => "decimal",
+                    SQL_BINARY => "binary",
+                    SQL_VARBINARY => "varbinary",
+                    SQL_GUID => "uniqueidentifier",
+                    _ => "varchar",
+                },
             };
             write_str_attr(type_name)
         }
@@ -933,7 +968,7 @@ pub extern "C" fn SQLColAttributeW(
             write_str_w(&col.name)
         }
         SQL_DESC_CONCISE_TYPE | SQL_DESC_TYPE | SQL_COLUMN_TYPE => {
-            write_num(col.sql_type as SQLLEN)
+            write_num(col.ss_type.unwrap_or(col.sql_type) as SQLLEN)
         }
         SQL_DESC_LENGTH | SQL_COLUMN_LENGTH => write_num(col.size as SQLLEN),
         SQL_DESC_DISPLAY_SIZE | SQL_COLUMN_DISPLAY_SIZE => {
@@ -966,28 +1001,34 @@ pub extern "C" fn SQLColAttributeW(
         SQL_DESC_SEARCHABLE => write_num(3),
         SQL_DESC_UNSIGNED => write_num(0),
         SQL_DESC_UPDATABLE => write_num(0),
-        SQL_DESC_TABLE_NAME => write_str_w(""),
+        SQL_DESC_TABLE_NAME | SQL_DESC_BASE_TABLE_NAME => write_str_w(&col.base_table),
+        SQL_DESC_BASE_COLUMN_NAME => write_str_w(&col.base_column),
+        SQL_DESC_SCHEMA_NAME => write_str_w(&col.schema),
+        SQL_DESC_CATALOG_NAME => write_str_w(&col.catalog),
         SQL_DESC_TYPE_NAME => {
-            let type_name = match col.sql_type {
-                SQL_INTEGER => "int",
-                SQL_SMALLINT => "smallint",
-                SQL_TINYINT => "tinyint",
-                SQL_BIGINT => "bigint",
-                SQL_BIT => "bit",
-                SQL_DOUBLE | SQL_FLOAT => "float",
-                SQL_REAL => "real",
-                SQL_VARCHAR => "varchar",
-                SQL_CHAR => "char",
-                SQL_WVARCHAR => "nvarchar",
-                SQL_WCHAR => "nchar",
-                SQL_TYPE_TIMESTAMP => "datetime",
-                SQL_TYPE_DATE => "date",
-                SQL_TYPE_TIME => "time",
-                SQL_DECIMAL | SQL_NUMERIC => "decimal",
-                SQL_BINARY => "binary",
-                SQL_VARBINARY => "varbinary",
-                SQL_GUID => "uniqueidentifier",
-                _ => "varchar",
+            let type_name = match col.ss_type {
+                Some(SQL_SS_XML) => "xml",
+                _ => match col.sql_type {
+                    SQL_INTEGER => "int",
+                    SQL_SMALLINT => "smallint",
+                    SQL_TINYINT => "tinyint",
+                    SQL_BIGINT => "bigint",
+                    SQL_BIT => "bit",
+                    SQL_DOUBLE | SQL_FLOAT => "float",
+                    SQL_REAL => "real",
+                    SQL_VARCHAR => "varchar",
+                    SQL_CHAR => "char",
+                    SQL_WVARCHAR => "nvarchar",
+                    SQL_WCHAR => "nchar",
+                    SQL_TYPE_TIMESTAMP => "datetime",
+                    SQL_TYPE_DATE => "date",
+                    SQL_TYPE_TIME => "time",
+                    SQL_DECIMAL | SQL_NUMERIC => "decimal",
+                    SQL_BINARY => "binary",
+                    SQL_VARBINARY => "varbinary",
+                    SQL_GUID => "uniqueidentifier",
+                    _ => "varchar",
+                },
             };
             write_str_w(type_name)
         }
@@ -1129,13 +1170,20 @@ fn catalog_columns(
          c.name AS COLUMN_NAME, \
          tp.system_type_id AS DATA_TYPE, \
          tp.name AS TYPE_NAME, \
-         COALESCE(c.max_length, 0) AS COLUMN_SIZE, \
-         COALESCE(c.max_length, 0) AS BUFFER_LENGTH, \
+         CASE \
+           WHEN tp.name IN ('nvarchar','nchar','ntext') THEN \
+             CASE WHEN c.max_length = -1 THEN 2147483647 ELSE c.max_length / 2 END \
+           WHEN c.max_length = -1 THEN 2147483647 \
+           ELSE COALESCE(c.max_length, 0) END AS COLUMN_SIZE, \
+         CASE WHEN c.max_length = -1 THEN 2147483647 ELSE COALESCE(c.max_length, 0) END AS BUFFER_LENGTH, \
          c.scale AS DECIMAL_DIGITS, \
          10 AS NUM_PREC_RADIX, \
          CASE c.is_nullable WHEN 1 THEN 1 ELSE 0 END AS NULLABLE, \
          CAST(NULL AS NVARCHAR(1)) AS REMARKS, \
-         c.column_id AS ORDINAL_POSITION \
+         c.column_id AS ORDINAL_POSITION, \
+         CASE WHEN tp.name IN ('varchar','nvarchar','char','nchar','text','ntext','binary','varbinary','image') \
+           THEN CAST(CASE WHEN c.max_length = -1 THEN 2147483647 ELSE c.max_length END AS BIGINT) \
+           ELSE CAST(NULL AS BIGINT) END AS CHAR_OCTET_LENGTH \
          FROM sys.all_columns c \
          JOIN sys.all_objects o ON c.object_id = o.object_id \
          JOIN sys.schemas s ON o.schema_id = s.schema_id \
@@ -1282,36 +1330,452 @@ pub extern "C" fn SQLExecute(hstmt: SQLHSTMT) -> SQLRETURN {
         }
     };
 
-    // Substitute bound parameters
+    // Bindings are left in place (not cleared here) so a caller can rebind
+    // the same pointers with new buffer contents and call SQLExecute again
+    // without another SQLBindParameter; SQLFreeStmt(SQL_RESET_PARAMS) is the
+    // explicit way to drop them.
+    exec_with_bound_params(stmt, &sql)
+}
+
+/// Substitute `stmt.bound_params` into `sql` (if any are bound) and run it,
+/// reporting per-row status through `SQL_ATTR_PARAM_STATUS_PTR`/
+/// `SQL_ATTR_PARAMS_PROCESSED_PTR` when `SQL_ATTR_PARAMSET_SIZE` > 1. Shared
+/// by `SQLExecute` (prepared statements) and `SQLExecDirect`/`SQLExecDirectW`,
+/// since `SQLBindParameter` may be called before either.
+fn exec_with_bound_params(stmt: &mut Statement, sql: &str) -> SQLRETURN {
+    // If any bound parameter's indicator marks it as data-at-execution, the
+    // app hasn't supplied its value yet — park the SQL and make it come back
+    // through SQLParamData/SQLPutData instead of running now.
+    let pending: Vec<u16> = stmt
+        .bound_params
+        .iter()
+        .filter(|p| !p.len_ind_ptr.is_null() && is_data_at_exec(unsafe { *p.len_ind_ptr }))
+        .map(|p| p.param_number)
+        .collect();
+    if !pending.is_empty() {
+        stmt.dae_sql = Some(sql.to_string());
+        stmt.dae_params_needed = pending;
+        stmt.dae_current_idx = 0;
+        stmt.dae_collected.clear();
+        stmt.dae_current_buf.clear();
+        return SQL_NEED_DATA;
+    }
+
+    // SQL_ATTR_PARAMSET_SIZE > 1 means each value_ptr/len_ind_ptr is the base
+    // of a column-wise array (one element per row, stride ==  the bound
+    // buffer_length); expand the statement's single VALUES (...) tuple into
+    // one tuple per row in that case.
+    let paramset_size = std::cmp::max(1, stmt.paramset_size);
     let final_sql = if stmt.bound_params.is_empty() {
-        sql
+        sql.to_string()
+    } else if paramset_size == 1 {
+        // Route through sp_executesql for correct typing, server-side plan
+        // reuse and injection safety; the textual-substitution path below is
+        // kept as the fallback array-bind expansion needs.
+        build_sp_executesql(sql, &stmt.bound_params)
     } else {
-        substitute_params(&sql, &stmt.bound_params)
+        expand_array_params(sql, &stmt.bound_params, paramset_size)
     };
 
     let ret = execute::exec_direct(stmt, &final_sql);
-    // Reset params after execute
-    stmt.bound_params.clear();
+
+    if paramset_size > 1 {
+        let status = if ret == SQL_SUCCESS || ret == SQL_SUCCESS_WITH_INFO {
+            SQL_PARAM_SUCCESS
+        } else {
+            SQL_PARAM_ERROR
+        };
+        if !stmt.param_status_ptr.is_null() {
+            unsafe {
+                for i in 0..paramset_size {
+                    *stmt.param_status_ptr.add(i) = status;
+                }
+            }
+        }
+        if !stmt.params_processed_ptr.is_null() {
+            unsafe {
+                *stmt.params_processed_ptr = paramset_size as SQLULEN;
+            }
+        }
+    }
+
     ret
 }
 
-fn substitute_params(sql: &str, params: &[BoundParam]) -> String {
-    let mut result = String::with_capacity(sql.len() + 64);
-    let mut param_idx = 0u16;
-    for ch in sql.chars() {
-        if ch == '?' {
-            param_idx += 1;
-            if let Some(param) = params.iter().find(|p| p.param_number == param_idx) {
-                let val = read_param_value(param);
-                result.push_str(&val);
+/// True when a `str_len_or_ind` value marks its parameter as data-at-exec
+/// (`SQL_DATA_AT_EXEC`, or `SQL_LEN_DATA_AT_EXEC(n)` which is always `<=
+/// SQL_DATA_AT_EXEC`), as opposed to an ordinary byte count, `SQL_NULL_DATA`,
+/// or `SQL_NTS`.
+fn is_data_at_exec(ind: SQLLEN) -> bool {
+    ind != SQL_NULL_DATA && ind != SQL_NTS && ind <= SQL_DATA_AT_EXEC
+}
+
+/// Render a data-at-exec parameter's accumulated `SQLPutData` bytes as a
+/// T-SQL literal. `SQL_C_BINARY` and `SQL_C_WCHAR` render as
+/// `read_param_value` renders them; every other value_type is read as 8-bit
+/// character text, as `read_param_value`'s `SQL_C_CHAR` arm reads it
+/// (unquoted for numeric parameter types), so fixed-size C types such as
+/// `SQL_C_LONG` are not decoded from their binary layout.
+fn render_dae_literal(param: &BoundParam, data: &[u8]) -> String {
+    match param.value_type {
+        SQL_C_BINARY => {
+            let mut hex = String::with_capacity(2 + data.len() * 2);
+            hex.push_str("0x");
+            for b in data {
+                hex.push_str(&format!("{:02x}", b));
+            }
+            hex
+        }
+        SQL_C_WCHAR => {
+            let units: Vec<u16> = data
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            let s = String::from_utf16_lossy(&units);
+            format!("N'{}'", s.replace('\'', "''"))
+        }
+        _ => {
+            let s = String::from_utf8_lossy(data);
+            if matches!(
+                param.parameter_type,
+                SQL_INTEGER
+                    | SQL_SMALLINT
+                    | SQL_BIGINT
+                    | SQL_TINYINT
+                    | SQL_DOUBLE
+                    | SQL_FLOAT
+                    | SQL_REAL
+                    | SQL_DECIMAL
+                    | SQL_NUMERIC
+                    | SQL_BIT
+            ) {
+                s.to_string()
             } else {
-                result.push_str("NULL");
+                format!("N'{}'", s.replace('\'', "''"))
             }
-        } else {
-            result.push(ch);
         }
     }
-    result
+}
+
+/// Once every data-at-exec parameter has been collected via `SQLPutData`,
+/// build the final SQL (the now-collected DAE values alongside any
+/// ordinarily-bound parameters) and run it. Mirrors `build_sp_executesql`'s
+/// `@Pn`-rewriting, but pulls each `@Pn`'s value from `dae_collected` when
+/// that parameter went through the DAE path.
+fn finalize_dae(stmt: &mut Statement) -> SQLRETURN {
+    let sql = stmt.dae_sql.take().unwrap_or_default();
+
+    let (rewritten, param_count) = rewrite_placeholders(&sql, |n| format!("@P{}", n));
+
+    let final_sql = if param_count == 0 {
+        format!("EXEC sp_executesql N'{}'", rewritten.replace('\'', "''"))
+    } else {
+        let mut decls = Vec::with_capacity(param_count as usize);
+        let mut assigns = Vec::with_capacity(param_count as usize);
+        for i in 1..=param_count {
+            match stmt.bound_params.iter().find(|p| p.param_number == i) {
+                Some(param) => {
+                    decls.push(format!("@P{} {}", i, sql_type_decl(param)));
+                    let value = match stmt.dae_collected.iter().find(|(n, _)| *n == i) {
+                        Some((_, v)) => v.clone(),
+                        None => read_param_value_at(param, 0),
+                    };
+                    assigns.push(format!("@P{} = {}", i, value));
+                }
+                None => {
+                    decls.push(format!("@P{} nvarchar(max)", i));
+                    assigns.push(format!("@P{} = NULL", i));
+                }
+            }
+        }
+        format!(
+            "EXEC sp_executesql N'{}', N'{}', {}",
+            rewritten.replace('\'', "''"),
+            decls.join(", ").replace('\'', "''"),
+            assigns.join(", ")
+        )
+    };
+
+    stmt.dae_params_needed.clear();
+    stmt.dae_current_idx = 0;
+    stmt.dae_collected.clear();
+    execute::exec_direct(stmt, &final_sql)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn SQLParamData(hstmt: SQLHSTMT, value_ptr_out: *mut SQLPOINTER) -> SQLRETURN {
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+
+    // If the previous SQLParamData token already has bytes from SQLPutData,
+    // that parameter is done — commit it and move to the next pending one.
+    if stmt.dae_current_idx < stmt.dae_params_needed.len() && !stmt.dae_current_buf.is_empty() {
+        let param_number = stmt.dae_params_needed[stmt.dae_current_idx];
+        let literal = match stmt
+            .bound_params
+            .iter()
+            .find(|p| p.param_number == param_number)
+        {
+            Some(param) => render_dae_literal(param, &stmt.dae_current_buf),
+            None => "NULL".to_string(),
+        };
+        stmt.dae_collected.push((param_number, literal));
+        stmt.dae_current_buf.clear();
+        stmt.dae_current_idx += 1;
+    }
+
+    if stmt.dae_current_idx >= stmt.dae_params_needed.len() {
+        return finalize_dae(stmt);
+    }
+
+    let param_number = stmt.dae_params_needed[stmt.dae_current_idx];
+    if let Some(param) = stmt
+        .bound_params
+        .iter()
+        .find(|p| p.param_number == param_number)
+    {
+        if !value_ptr_out.is_null() {
+            unsafe {
+                *value_ptr_out = param.value_ptr;
+            }
+        }
+    }
+    SQL_NEED_DATA
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn SQLPutData(hstmt: SQLHSTMT, data: SQLPOINTER, str_len_or_ind: SQLLEN) -> SQLRETURN {
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    if str_len_or_ind == SQL_NULL_DATA || data.is_null() {
+        return SQL_SUCCESS;
+    }
+    let len = if str_len_or_ind == SQL_NTS {
+        let ptr = data as *const u8;
+        let mut n = 0usize;
+        unsafe {
+            while *ptr.add(n) != 0 {
+                n += 1;
+            }
+        }
+        n
+    } else {
+        str_len_or_ind as usize
+    };
+    let slice = unsafe { std::slice::from_raw_parts(data as *const u8, len) };
+    stmt.dae_current_buf.extend_from_slice(slice);
+    SQL_SUCCESS
+}
+
+/// Derive the T-SQL type declaration `sp_executesql` needs for a bound
+/// parameter's `@Pn`, from `parameter_type`/`column_size`/`decimal_digits` —
+/// the same triple `SQLBindParameter` recorded them from.
+fn sql_type_decl(param: &BoundParam) -> String {
+    match param.parameter_type {
+        SQL_INTEGER => "int".to_string(),
+        SQL_SMALLINT => "smallint".to_string(),
+        SQL_TINYINT => "tinyint".to_string(),
+        SQL_BIGINT => "bigint".to_string(),
+        SQL_DOUBLE | SQL_FLOAT => "float".to_string(),
+        SQL_REAL => "real".to_string(),
+        SQL_BIT => "bit".to_string(),
+        SQL_DECIMAL | SQL_NUMERIC => {
+            let precision = if param.column_size == 0 { 18 } else { param.column_size };
+            let scale = param.decimal_digits.max(0);
+            format!("decimal({},{})", precision, scale)
+        }
+        SQL_TYPE_TIMESTAMP => "datetime2".to_string(),
+        SQL_TYPE_DATE => "date".to_string(),
+        SQL_TYPE_TIME => "time".to_string(),
+        SQL_GUID => "uniqueidentifier".to_string(),
+        SQL_BINARY => format!("binary({})", if param.column_size == 0 { 8000 } else { param.column_size }),
+        SQL_VARBINARY | SQL_LONGVARBINARY => match param.column_size {
+            0 | 8001.. => "varbinary(max)".to_string(),
+            n => format!("varbinary({})", n),
+        },
+        SQL_CHAR => format!("char({})", if param.column_size == 0 { 1 } else { param.column_size }),
+        SQL_VARCHAR | SQL_LONGVARCHAR => match param.column_size {
+            0 | 8001.. => "varchar(max)".to_string(),
+            n => format!("varchar({})", n),
+        },
+        SQL_WCHAR => format!("nchar({})", if param.column_size == 0 { 1 } else { param.column_size }),
+        // SQL_WVARCHAR and anything unrecognized default to nvarchar, the
+        // widest-compatible text type for a value whose true type we can't
+        // otherwise pin down.
+        _ => match param.column_size {
+            0 | 4001.. => "nvarchar(max)".to_string(),
+            n => format!("nvarchar({})", n),
+        },
+    }
+}
+
+/// Scans `sql` for `?` placeholders, invoking `replace` for each one found
+/// outside a single-quoted string literal (`'...'`, with `''` as an escaped
+/// quote) or a bracketed identifier (`[...]`, with `]]` as an escaped
+/// bracket) — T-SQL's two quoting forms — so a literal `?` embedded in a
+/// string or identifier is never mistaken for a bind placeholder. Returns
+/// the rewritten SQL alongside the number of real placeholders found.
+fn rewrite_placeholders(sql: &str, mut replace: impl FnMut(u16) -> String) -> (String, u16) {
+    let mut result = String::with_capacity(sql.len() + 16);
+    let mut param_count = 0u16;
+    let mut in_string = false;
+    let mut in_bracket = false;
+    let mut chars = sql.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' if !in_bracket => {
+                result.push(ch);
+                if in_string && chars.peek() == Some(&'\'') {
+                    result.push(chars.next().unwrap());
+                } else {
+                    in_string = !in_string;
+                }
+            }
+            '[' if !in_string && !in_bracket => {
+                in_bracket = true;
+                result.push(ch);
+            }
+            ']' if in_bracket => {
+                result.push(ch);
+                if chars.peek() == Some(&']') {
+                    result.push(chars.next().unwrap());
+                } else {
+                    in_bracket = false;
+                }
+            }
+            '?' if !in_string && !in_bracket => {
+                param_count += 1;
+                result.push_str(&replace(param_count));
+            }
+            _ => result.push(ch),
+        }
+    }
+    (result, param_count)
+}
+
+/// Wrap `sql` (with its `?` placeholders) as `EXEC sp_executesql N'...',
+/// N'@P1 type, ...', @P1 = val, ...` — typed parameters instead of spliced
+/// literals, for correct precision/collation/binary length and server-side
+/// plan reuse. Used for the single-row execute path; array-bound execution
+/// falls back to `expand_array_params`'s textual substitution instead, since
+/// `sp_executesql` has no notion of a repeated-tuple parameter array.
+fn build_sp_executesql(sql: &str, params: &[BoundParam]) -> String {
+    let (rewritten, param_count) = rewrite_placeholders(sql, |n| format!("@P{}", n));
+
+    if param_count == 0 {
+        return format!("EXEC sp_executesql N'{}'", rewritten.replace('\'', "''"));
+    }
+
+    let mut decls = Vec::with_capacity(param_count as usize);
+    let mut assigns = Vec::with_capacity(param_count as usize);
+    for i in 1..=param_count {
+        match params.iter().find(|p| p.param_number == i) {
+            Some(param) => {
+                decls.push(format!("@P{} {}", i, sql_type_decl(param)));
+                assigns.push(format!("@P{} = {}", i, read_param_value_at(param, 0)));
+            }
+            None => {
+                decls.push(format!("@P{} nvarchar(max)", i));
+                assigns.push(format!("@P{} = NULL", i));
+            }
+        }
+    }
+
+    format!(
+        "EXEC sp_executesql N'{}', N'{}', {}",
+        rewritten.replace('\'', "''"),
+        decls.join(", ").replace('\'', "''"),
+        assigns.join(", ")
+    )
+}
+
+fn substitute_params_row(sql: &str, params: &[BoundParam], row: usize) -> String {
+    rewrite_placeholders(sql, |param_idx| {
+        match params.iter().find(|p| p.param_number == param_idx) {
+            Some(param) => read_param_value_at(param, row),
+            None => "NULL".to_string(),
+        }
+    })
+    .0
+}
+
+/// Expand a single-row `VALUES (...)` tail into `paramset_size` tuples, one
+/// per row of a column-wise bound parameter array, joined by `, `. Falls
+/// back to a single substituted row if no `VALUES (...)` tuple is found
+/// (e.g. a parameterized `UPDATE`/`WHERE` clause, which array binding can't
+/// help since a single execution can only apply one set of values).
+fn expand_array_params(sql: &str, params: &[BoundParam], paramset_size: usize) -> String {
+    let upper = sql.to_uppercase();
+    let Some(values_pos) = upper.find("VALUES") else {
+        return substitute_params_row(sql, params, 0);
+    };
+    let after_values = &sql[values_pos + "VALUES".len()..];
+    let Some(open) = after_values.find('(') else {
+        return substitute_params_row(sql, params, 0);
+    };
+
+    let mut depth = 0i32;
+    let mut close = None;
+    for (i, ch) in after_values.char_indices() {
+        if i < open {
+            continue;
+        }
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        return substitute_params_row(sql, params, 0);
+    };
+
+    let tuple_template = &after_values[open..=close];
+    let prefix = &sql[..values_pos + "VALUES".len()];
+    let suffix = &after_values[close + 1..];
+
+    let tuples: Vec<String> = (0..paramset_size)
+        .map(|row| substitute_params_row(tuple_template, params, row))
+        .collect();
+    format!("{} {}{}", prefix, tuples.join(", "), suffix)
+}
+
+/// Re-point a `BoundParam` at row `row` of its bound array (stride ==
+/// `buffer_length`, the column-wise layout `SQL_ATTR_PARAMSET_SIZE` implies)
+/// and read its value through the existing scalar path.
+fn read_param_value_at(param: &BoundParam, row: usize) -> String {
+    if row == 0 {
+        return read_param_value(param);
+    }
+    if param.value_ptr.is_null() {
+        return "NULL".to_string();
+    }
+    let stride = param.buffer_length.max(0) as usize;
+    let row_param = BoundParam {
+        param_number: param.param_number,
+        value_type: param.value_type,
+        parameter_type: param.parameter_type,
+        column_size: param.column_size,
+        decimal_digits: param.decimal_digits,
+        value_ptr: unsafe { (param.value_ptr as *mut u8).add(row * stride) as SQLPOINTER },
+        buffer_length: param.buffer_length,
+        len_ind_ptr: if param.len_ind_ptr.is_null() {
+            ptr::null_mut()
+        } else {
+            unsafe { param.len_ind_ptr.add(row) }
+        },
+    };
+    read_param_value(&row_param)
 }
 
 fn read_param_value(param: &BoundParam) -> String {
@@ -1349,6 +1813,78 @@ fn read_param_value(param: &BoundParam) -> String {
                 let v = *(param.value_ptr as *const f32);
                 v.to_string()
             }
+            SQL_C_TYPE_DATE => {
+                let d = *(param.value_ptr as *const SqlDateStruct);
+                format!("'{:04}-{:02}-{:02}'", d.year, d.month, d.day)
+            }
+            SQL_C_TYPE_TIME => {
+                let t = *(param.value_ptr as *const SqlTimeStruct);
+                format!("'{:02}:{:02}:{:02}'", t.hour, t.minute, t.second)
+            }
+            SQL_C_TYPE_TIMESTAMP => {
+                let ts = *(param.value_ptr as *const SqlTimestampStruct);
+                format!(
+                    "'{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:07}'",
+                    ts.year,
+                    ts.month,
+                    ts.day,
+                    ts.hour,
+                    ts.minute,
+                    ts.second,
+                    ts.fraction / 100
+                )
+            }
+            SQL_C_BINARY => {
+                let len_ind = if !param.len_ind_ptr.is_null() {
+                    *param.len_ind_ptr
+                } else {
+                    0
+                };
+                let ptr = param.value_ptr as *const u8;
+                let slice = std::slice::from_raw_parts(ptr, len_ind.max(0) as usize);
+                let mut hex = String::with_capacity(2 + slice.len() * 2);
+                hex.push_str("0x");
+                for b in slice {
+                    hex.push_str(&format!("{:02x}", b));
+                }
+                hex
+            }
+            SQL_C_GUID => {
+                let g = *(param.value_ptr as *const SqlGuid);
+                format!(
+                    "'{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}'",
+                    g.data1,
+                    g.data2,
+                    g.data3,
+                    g.data4[0],
+                    g.data4[1],
+                    g.data4[2],
+                    g.data4[3],
+                    g.data4[4],
+                    g.data4[5],
+                    g.data4[6],
+                    g.data4[7]
+                )
+            }
+            SQL_C_NUMERIC => {
+                let n = *(param.value_ptr as *const SqlNumericStruct);
+                let mut magnitude: u128 = 0;
+                for byte in n.val.iter().rev() {
+                    magnitude = (magnitude << 8) | (*byte as u128);
+                }
+                let mut digits = magnitude.to_string();
+                let scale = n.scale as usize;
+                if scale > 0 {
+                    while digits.len() <= scale {
+                        digits.insert(0, '0');
+                    }
+                    digits.insert(digits.len() - scale, '.');
+                }
+                if n.sign == 0 {
+                    digits.insert(0, '-');
+                }
+                digits
+            }
             SQL_C_WCHAR => {
                 // UTF-16 string
                 let len_ind = if !param.len_ind_ptr.is_null() {
@@ -1413,18 +1949,34 @@ fn read_param_value(param: &BoundParam) -> String {
     }
 }
 
+// Recorded bindings are consumed by `fetch::fetch_block`, which drives them
+// through the same per-type conversion (and truncation/NULL indicator
+// handling) as `SQLGetData` — see `fetch::get_data`.
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLBindCol(
     hstmt: SQLHSTMT,
-    _col_number: SQLUSMALLINT,
-    _target_type: SQLSMALLINT,
-    _target_value: SQLPOINTER,
-    _buffer_length: SQLLEN,
-    _str_len_or_ind: *mut SQLLEN,
+    col_number: SQLUSMALLINT,
+    target_type: SQLSMALLINT,
+    target_value: SQLPOINTER,
+    buffer_length: SQLLEN,
+    str_len_or_ind: *mut SQLLEN,
 ) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    // A null target_value unbinds the column, same as SQLFreeStmt(SQL_UNBIND)
+    // would for it alone.
+    stmt.bound_cols.retain(|c| c.col_number != col_number);
+    if !target_value.is_null() {
+        stmt.bound_cols.push(BoundColumn {
+            col_number,
+            target_type,
+            target_value,
+            buffer_length,
+            str_len_or_ind,
+        });
+    }
     SQL_SUCCESS
 }
 
@@ -1433,15 +1985,17 @@ pub extern "C" fn SQLMoreResults(hstmt: SQLHSTMT) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
-    SQL_NO_DATA
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    fetch::more_results(stmt)
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn SQLGetTypeInfo(hstmt: SQLHSTMT, _data_type: SQLSMALLINT) -> SQLRETURN {
+pub extern "C" fn SQLGetTypeInfo(hstmt: SQLHSTMT, data_type: SQLSMALLINT) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
-    SQL_SUCCESS
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    catalog::get_type_info(stmt, data_type)
 }
 
 #[unsafe(no_mangle)]
@@ -1565,7 +2119,7 @@ pub extern "C" fn SQLDescribeColW(
     }
     if !data_type.is_null() {
         unsafe {
-            *data_type = col.sql_type;
+            *data_type = col.ss_type.unwrap_or(col.sql_type);
         }
     }
     if !column_size.is_null() {
@@ -1590,15 +2144,23 @@ pub extern "C" fn SQLDescribeColW(
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLGetDiagField(
-    _handle_type: SQLSMALLINT,
-    _handle: SQLHANDLE,
-    _rec_number: SQLSMALLINT,
-    _diag_identifier: SQLSMALLINT,
-    _diag_info: SQLPOINTER,
-    _buffer_length: SQLSMALLINT,
-    _string_length: *mut SQLSMALLINT,
+    handle_type: SQLSMALLINT,
+    handle: SQLHANDLE,
+    rec_number: SQLSMALLINT,
+    diag_identifier: SQLSMALLINT,
+    diag_info: SQLPOINTER,
+    buffer_length: SQLSMALLINT,
+    string_length: *mut SQLSMALLINT,
 ) -> SQLRETURN {
-    SQL_NO_DATA
+    diagnostics::get_diag_field(
+        handle_type,
+        handle,
+        rec_number,
+        diag_identifier,
+        diag_info,
+        buffer_length,
+        string_length,
+    )
 }
 
 #[unsafe(no_mangle)]
@@ -1624,25 +2186,10 @@ pub extern "C" fn SQLGetDiagFieldW(
 
 // ── SQLEndTran (needed by some apps) ────────────────────────────────
 
-#[unsafe(no_mangle)]
-pub extern "C" fn SQLEndTran(
-    handle_type: SQLSMALLINT,
-    handle: SQLHANDLE,
-    completion_type: SQLSMALLINT,
-) -> SQLRETURN {
-    if handle.is_null() {
-        return SQL_INVALID_HANDLE;
-    }
-
-    let conn = match handle_type {
-        SQL_HANDLE_DBC => unsafe { &mut *(handle as *mut Connection) },
-        SQL_HANDLE_ENV => {
-            // For ENV handle, commit/rollback all connections — simplified: just succeed
-            return SQL_SUCCESS;
-        }
-        _ => return SQL_INVALID_HANDLE,
-    };
-
+/// Commits or rolls back `conn` if it has an open transaction. Shared by
+/// `SQLEndTran`'s `SQL_HANDLE_DBC` case and its `SQL_HANDLE_ENV` case, which
+/// applies this to every connection the environment owns.
+fn end_tran_conn(conn: &mut Connection, completion_type: SQLSMALLINT) -> SQLRETURN {
     if !conn.in_transaction {
         return SQL_SUCCESS;
     }
@@ -1668,6 +2215,20 @@ pub extern "C" fn SQLEndTran(
 
     conn.in_transaction = false;
 
+    let event = TraceEvent::Transaction {
+        server: &conn.server,
+        database: &conn.database,
+        kind: if completion_type == SQL_COMMIT {
+            "COMMIT"
+        } else {
+            "ROLLBACK"
+        },
+    };
+    if let Some(cb) = conn.trace_callback.as_ref() {
+        cb(&event);
+    }
+    crate::trace::emit(&event);
+
     match result {
         Ok(()) => SQL_SUCCESS,
         Err(msg) => {
@@ -1681,6 +2242,36 @@ pub extern "C" fn SQLEndTran(
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn SQLEndTran(
+    handle_type: SQLSMALLINT,
+    handle: SQLHANDLE,
+    completion_type: SQLSMALLINT,
+) -> SQLRETURN {
+    if handle.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+
+    match handle_type {
+        SQL_HANDLE_DBC => {
+            let conn = unsafe { &mut *(handle as *mut Connection) };
+            end_tran_conn(conn, completion_type)
+        }
+        SQL_HANDLE_ENV => {
+            let env = unsafe { &mut *(handle as *mut Environment) };
+            let mut ret = SQL_SUCCESS;
+            for &conn_ptr in &env.connections {
+                let conn = unsafe { &mut *conn_ptr };
+                if end_tran_conn(conn, completion_type) != SQL_SUCCESS {
+                    ret = SQL_ERROR;
+                }
+            }
+            ret
+        }
+        _ => SQL_INVALID_HANDLE,
+    }
+}
+
 // ── SQLCloseCursor ──────────────────────────────────────────────────
 
 #[unsafe(no_mangle)]
@@ -1740,6 +2331,56 @@ pub extern "C" fn SQLNumParams(hstmt: SQLHSTMT, param_count: *mut SQLSMALLINT) -
     SQL_SUCCESS
 }
 
+// ── SQLDescribeParam ────────────────────────────────────────────────
+
+#[unsafe(no_mangle)]
+pub extern "C" fn SQLDescribeParam(
+    hstmt: SQLHSTMT,
+    param_number: SQLUSMALLINT,
+    data_type: *mut SQLSMALLINT,
+    parameter_size: *mut SQLULEN,
+    decimal_digits: *mut SQLSMALLINT,
+    nullable: *mut SQLSMALLINT,
+) -> SQLRETURN {
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let param_count = stmt
+        .prepared_sql
+        .as_ref()
+        .map(|s| s.matches('?').count() as SQLUSMALLINT)
+        .unwrap_or(0);
+    if param_number == 0 || param_number > param_count {
+        stmt.diagnostics.push(DiagRecord {
+            state: "07009".to_string(),
+            native_error: 0,
+            message: "Invalid descriptor index".to_string(),
+        });
+        return SQL_ERROR;
+    }
+    // Only parameters already bound via SQLBindParameter have a type to
+    // report; the driver has no query-plan introspection to describe an
+    // unbound placeholder, so default to a permissive, generously-sized
+    // SQL_VARCHAR.
+    let bound = stmt.bound_params.iter().find(|p| p.param_number == param_number);
+    unsafe {
+        if !data_type.is_null() {
+            *data_type = bound.map(|p| p.parameter_type).unwrap_or(SQL_VARCHAR);
+        }
+        if !parameter_size.is_null() {
+            *parameter_size = bound.map(|p| p.column_size).unwrap_or(4000);
+        }
+        if !decimal_digits.is_null() {
+            *decimal_digits = bound.map(|p| p.decimal_digits).unwrap_or(0);
+        }
+        if !nullable.is_null() {
+            *nullable = SQL_NULLABLE_UNKNOWN;
+        }
+    }
+    SQL_SUCCESS
+}
+
 // ── SQLGetFunctions ─────────────────────────────────────────────────
 
 #[unsafe(no_mangle)]
@@ -1815,13 +2456,13 @@ pub extern "C" fn SQLGetFunctions(
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLSpecialColumns(
     hstmt: SQLHSTMT,
-    _id_type: SQLUSMALLINT,
-    _catalog: *const SQLCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLCHAR,
-    _table_len: SQLSMALLINT,
+    id_type: SQLUSMALLINT,
+    catalog: *const SQLCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLCHAR,
+    table_len: SQLSMALLINT,
     _scope: SQLUSMALLINT,
     _nullable: SQLUSMALLINT,
 ) -> SQLRETURN {
@@ -1829,219 +2470,244 @@ pub extern "C" fn SQLSpecialColumns(
         return SQL_INVALID_HANDLE;
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
-    stmt.columns.clear();
-    stmt.rows.clear();
-    stmt.row_index = -1;
-    stmt.executed = true;
-    SQL_SUCCESS
+    let cat = unsafe { sql_str(catalog, catalog_len) };
+    let sch = unsafe { sql_str(schema, schema_len) };
+    let tbl = unsafe { sql_str(table, table_len) };
+    catalog::special_columns(stmt, id_type, &cat, &sch, &tbl)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLSpecialColumnsW(
     hstmt: SQLHSTMT,
     id_type: SQLUSMALLINT,
-    _catalog: *const SQLWCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLWCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLWCHAR,
-    _table_len: SQLSMALLINT,
+    catalog: *const SQLWCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLWCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLWCHAR,
+    table_len: SQLSMALLINT,
     scope: SQLUSMALLINT,
     nullable: SQLUSMALLINT,
 ) -> SQLRETURN {
-    SQLSpecialColumns(
-        hstmt,
-        id_type,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        scope,
-        nullable,
-    )
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let cat = wchar_to_string(catalog, catalog_len);
+    let sch = wchar_to_string(schema, schema_len);
+    let tbl = wchar_to_string(table, table_len);
+    let _ = (scope, nullable);
+    crate::catalog::special_columns(stmt, id_type, &cat, &sch, &tbl)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLStatistics(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLCHAR,
-    _table_len: SQLSMALLINT,
-    _unique: SQLUSMALLINT,
+    catalog: *const SQLCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLCHAR,
+    table_len: SQLSMALLINT,
+    unique: SQLUSMALLINT,
     _reserved: SQLUSMALLINT,
 ) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
-    stmt.columns.clear();
-    stmt.rows.clear();
-    stmt.row_index = -1;
-    stmt.executed = true;
-    SQL_SUCCESS
+    let cat = unsafe { sql_str(catalog, catalog_len) };
+    let sch = unsafe { sql_str(schema, schema_len) };
+    let tbl = unsafe { sql_str(table, table_len) };
+    catalog::statistics(stmt, &cat, &sch, &tbl, unique)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLStatisticsW(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLWCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLWCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLWCHAR,
-    _table_len: SQLSMALLINT,
+    catalog: *const SQLWCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLWCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLWCHAR,
+    table_len: SQLSMALLINT,
     unique: SQLUSMALLINT,
     reserved: SQLUSMALLINT,
 ) -> SQLRETURN {
-    SQLStatistics(
-        hstmt,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        unique,
-        reserved,
-    )
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let cat = wchar_to_string(catalog, catalog_len);
+    let sch = wchar_to_string(schema, schema_len);
+    let tbl = wchar_to_string(table, table_len);
+    let _ = reserved;
+    catalog::statistics(stmt, &cat, &sch, &tbl, unique)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLPrimaryKeys(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLCHAR,
-    _table_len: SQLSMALLINT,
+    catalog: *const SQLCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLCHAR,
+    table_len: SQLSMALLINT,
 ) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
-    stmt.columns.clear();
-    stmt.rows.clear();
-    stmt.row_index = -1;
-    stmt.executed = true;
-    SQL_SUCCESS
+    let cat = unsafe { sql_str(catalog, catalog_len) };
+    let sch = unsafe { sql_str(schema, schema_len) };
+    let tbl = unsafe { sql_str(table, table_len) };
+    catalog::primary_keys(stmt, &cat, &sch, &tbl)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLPrimaryKeysW(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLWCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLWCHAR,
-    _schema_len: SQLSMALLINT,
-    _table: *const SQLWCHAR,
-    _table_len: SQLSMALLINT,
+    catalog: *const SQLWCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLWCHAR,
+    schema_len: SQLSMALLINT,
+    table: *const SQLWCHAR,
+    table_len: SQLSMALLINT,
 ) -> SQLRETURN {
-    SQLPrimaryKeys(hstmt, ptr::null(), 0, ptr::null(), 0, ptr::null(), 0)
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let cat = wchar_to_string(catalog, catalog_len);
+    let sch = wchar_to_string(schema, schema_len);
+    let tbl = wchar_to_string(table, table_len);
+    catalog::primary_keys(stmt, &cat, &sch, &tbl)
 }
 
-// ── SQLForeignKeys / SQLProcedures (stubs) ──────────────────────────
+// ── SQLForeignKeys / SQLProcedures ───────────────────────────────────
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLForeignKeys(
     hstmt: SQLHSTMT,
-    _pk_cat: *const SQLCHAR,
-    _pk_cat_len: SQLSMALLINT,
-    _pk_sch: *const SQLCHAR,
-    _pk_sch_len: SQLSMALLINT,
-    _pk_tbl: *const SQLCHAR,
-    _pk_tbl_len: SQLSMALLINT,
-    _fk_cat: *const SQLCHAR,
-    _fk_cat_len: SQLSMALLINT,
-    _fk_sch: *const SQLCHAR,
-    _fk_sch_len: SQLSMALLINT,
-    _fk_tbl: *const SQLCHAR,
-    _fk_tbl_len: SQLSMALLINT,
+    pk_cat: *const SQLCHAR,
+    pk_cat_len: SQLSMALLINT,
+    pk_sch: *const SQLCHAR,
+    pk_sch_len: SQLSMALLINT,
+    pk_tbl: *const SQLCHAR,
+    pk_tbl_len: SQLSMALLINT,
+    fk_cat: *const SQLCHAR,
+    fk_cat_len: SQLSMALLINT,
+    fk_sch: *const SQLCHAR,
+    fk_sch_len: SQLSMALLINT,
+    fk_tbl: *const SQLCHAR,
+    fk_tbl_len: SQLSMALLINT,
 ) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
-    stmt.columns.clear();
-    stmt.rows.clear();
-    stmt.row_index = -1;
-    stmt.executed = true;
-    SQL_SUCCESS
+    let pk_cat = unsafe { sql_str(pk_cat, pk_cat_len) };
+    let pk_sch = unsafe { sql_str(pk_sch, pk_sch_len) };
+    let pk_tbl = unsafe { sql_str(pk_tbl, pk_tbl_len) };
+    let fk_cat = unsafe { sql_str(fk_cat, fk_cat_len) };
+    let fk_sch = unsafe { sql_str(fk_sch, fk_sch_len) };
+    let fk_tbl = unsafe { sql_str(fk_tbl, fk_tbl_len) };
+    catalog::foreign_keys(stmt, &pk_cat, &pk_sch, &pk_tbl, &fk_cat, &fk_sch, &fk_tbl)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLForeignKeysW(
     hstmt: SQLHSTMT,
-    _pk_cat: *const SQLWCHAR,
-    _pk_cat_len: SQLSMALLINT,
-    _pk_sch: *const SQLWCHAR,
-    _pk_sch_len: SQLSMALLINT,
-    _pk_tbl: *const SQLWCHAR,
-    _pk_tbl_len: SQLSMALLINT,
-    _fk_cat: *const SQLWCHAR,
-    _fk_cat_len: SQLSMALLINT,
-    _fk_sch: *const SQLWCHAR,
-    _fk_sch_len: SQLSMALLINT,
-    _fk_tbl: *const SQLWCHAR,
-    _fk_tbl_len: SQLSMALLINT,
+    pk_cat: *const SQLWCHAR,
+    pk_cat_len: SQLSMALLINT,
+    pk_sch: *const SQLWCHAR,
+    pk_sch_len: SQLSMALLINT,
+    pk_tbl: *const SQLWCHAR,
+    pk_tbl_len: SQLSMALLINT,
+    fk_cat: *const SQLWCHAR,
+    fk_cat_len: SQLSMALLINT,
+    fk_sch: *const SQLWCHAR,
+    fk_sch_len: SQLSMALLINT,
+    fk_tbl: *const SQLWCHAR,
+    fk_tbl_len: SQLSMALLINT,
 ) -> SQLRETURN {
-    SQLForeignKeys(
-        hstmt,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-        ptr::null(),
-        0,
-    )
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let pk_cat = wchar_to_string(pk_cat, pk_cat_len);
+    let pk_sch = wchar_to_string(pk_sch, pk_sch_len);
+    let pk_tbl = wchar_to_string(pk_tbl, pk_tbl_len);
+    let fk_cat = wchar_to_string(fk_cat, fk_cat_len);
+    let fk_sch = wchar_to_string(fk_sch, fk_sch_len);
+    let fk_tbl = wchar_to_string(fk_tbl, fk_tbl_len);
+    catalog::foreign_keys(stmt, &pk_cat, &pk_sch, &pk_tbl, &fk_cat, &fk_sch, &fk_tbl)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLProcedures(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLCHAR,
-    _schema_len: SQLSMALLINT,
-    _proc: *const SQLCHAR,
-    _proc_len: SQLSMALLINT,
+    catalog: *const SQLCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLCHAR,
+    schema_len: SQLSMALLINT,
+    proc: *const SQLCHAR,
+    proc_len: SQLSMALLINT,
 ) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
-    stmt.columns.clear();
-    stmt.rows.clear();
-    stmt.row_index = -1;
-    stmt.executed = true;
-    SQL_SUCCESS
+    let cat = unsafe { sql_str(catalog, catalog_len) };
+    let sch = unsafe { sql_str(schema, schema_len) };
+    let prc = unsafe { sql_str(proc, proc_len) };
+    catalog::procedures(stmt, &cat, &sch, &prc)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLProceduresW(
     hstmt: SQLHSTMT,
-    _catalog: *const SQLWCHAR,
-    _catalog_len: SQLSMALLINT,
-    _schema: *const SQLWCHAR,
-    _schema_len: SQLSMALLINT,
-    _proc: *const SQLWCHAR,
-    _proc_len: SQLSMALLINT,
+    catalog: *const SQLWCHAR,
+    catalog_len: SQLSMALLINT,
+    schema: *const SQLWCHAR,
+    schema_len: SQLSMALLINT,
+    proc: *const SQLWCHAR,
+    proc_len: SQLSMALLINT,
 ) -> SQLRETURN {
-    SQLProcedures(hstmt, ptr::null(), 0, ptr::null(), 0, ptr::null(), 0)
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    let cat = wchar_to_string(catalog, catalog_len);
+    let sch = wchar_to_string(schema, schema_len);
+    let prc = wchar_to_string(proc, proc_len);
+    catalog::procedures(stmt, &cat, &sch, &prc)
 }
 
-// ── SQLBindParameter (stub) ─────────────────────────────────────────
+// ── SQLBindParameter ────────────────────────────────────────────────
+
+/// Infers the wire SQL type from a bound C buffer type, used when a caller
+/// passes `SQL_UNKNOWN_TYPE` for `parameter_type` instead of describing the
+/// target column itself.
+fn sql_type_for_c_type(c_type: SQLSMALLINT) -> SQLSMALLINT {
+    match c_type {
+        SQL_C_LONG | SQL_C_SLONG => SQL_INTEGER,
+        SQL_C_SHORT | SQL_C_SSHORT => SQL_SMALLINT,
+        SQL_C_STINYINT | SQL_C_UTINYINT => SQL_TINYINT,
+        SQL_C_SBIGINT | SQL_C_UBIGINT => SQL_BIGINT,
+        SQL_C_DOUBLE => SQL_DOUBLE,
+        SQL_C_FLOAT => SQL_REAL,
+        SQL_C_BIT => SQL_BIT,
+        SQL_C_BINARY => SQL_VARBINARY,
+        SQL_C_GUID => SQL_GUID,
+        SQL_C_TYPE_TIMESTAMP => SQL_TYPE_TIMESTAMP,
+        SQL_C_TYPE_DATE => SQL_TYPE_DATE,
+        SQL_C_TYPE_TIME => SQL_TYPE_TIME,
+        SQL_C_WCHAR => SQL_WVARCHAR,
+        SQL_C_CHAR | _ => SQL_VARCHAR,
+    }
+}
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLBindParameter(
@@ -2061,10 +2727,16 @@ pub extern "C" fn SQLBindParameter(
     }
     let stmt = unsafe { &mut *(hstmt as *mut Statement) };
 
+    let resolved_type = if parameter_type == SQL_UNKNOWN_TYPE {
+        sql_type_for_c_type(value_type)
+    } else {
+        parameter_type
+    };
+
     let param = BoundParam {
         param_number,
         value_type,
-        parameter_type,
+        parameter_type: resolved_type,
         column_size,
         decimal_digits,
         value_ptr: parameter_value,
@@ -2093,6 +2765,11 @@ pub extern "C" fn SQLCancel(hstmt: SQLHSTMT) -> SQLRETURN {
     if hstmt.is_null() {
         return SQL_INVALID_HANDLE;
     }
+    // Safe to call from a thread other than the one blocked in exec_direct:
+    // this only wakes the Notify the executing future is selecting against,
+    // it doesn't touch the connection itself.
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    stmt.cancel_token.notify_one();
     SQL_SUCCESS
 }
 
@@ -2100,17 +2777,45 @@ pub extern "C" fn SQLCancel(hstmt: SQLHSTMT) -> SQLRETURN {
 
 #[unsafe(no_mangle)]
 pub extern "C" fn SQLFetchScroll(
+    hstmt: SQLHSTMT,
+    fetch_orientation: SQLSMALLINT,
+    fetch_offset: SQLLEN,
+) -> SQLRETURN {
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    fetch::fetch_scroll(stmt, fetch_orientation, fetch_offset)
+}
+
+// ── SQLExtendedFetch (ODBC 2.x block-cursor compat) ────────────────
+
+#[unsafe(no_mangle)]
+pub extern "C" fn SQLExtendedFetch(
     hstmt: SQLHSTMT,
     fetch_orientation: SQLSMALLINT,
     _fetch_offset: SQLLEN,
+    row_count_ptr: *mut SQLULEN,
+    row_status_array: *mut SQLUSMALLINT,
 ) -> SQLRETURN {
-    if fetch_orientation == SQL_FETCH_NEXT {
-        SQLFetch(hstmt)
-    } else if hstmt.is_null() {
-        SQL_INVALID_HANDLE
-    } else {
-        SQL_ERROR
+    if hstmt.is_null() {
+        return SQL_INVALID_HANDLE;
+    }
+    if fetch_orientation != SQL_FETCH_NEXT {
+        return SQL_ERROR;
     }
+    let stmt = unsafe { &mut *(hstmt as *mut Statement) };
+    // Drive the same SQL_ATTR_ROW_ARRAY_SIZE block-fetch engine SQLBindCol
+    // callers use, just reporting through the legacy out-params instead of
+    // SQL_ATTR_ROWS_FETCHED_PTR / SQL_ATTR_ROW_STATUS_PTR.
+    let saved_rows_fetched_ptr = stmt.rows_fetched_ptr;
+    let saved_row_status_ptr = stmt.row_status_ptr;
+    stmt.rows_fetched_ptr = row_count_ptr;
+    stmt.row_status_ptr = row_status_array;
+    let ret = fetch::fetch(stmt);
+    stmt.rows_fetched_ptr = saved_rows_fetched_ptr;
+    stmt.row_status_ptr = saved_row_status_ptr;
+    ret
 }
 
 // ── SQLAllocConnect / SQLAllocEnv / SQLAllocStmt (ODBC 2.x compat) ──
@@ -0,0 +1,131 @@
+//! Opt-in, process-wide statement tracing, independent of the per-connection
+//! `SQL_ATTR_FURBALL_TRACE_CALLBACK` hook in `handle::Connection`. This sink
+//! is meant for embedders that want one place to observe every statement the
+//! driver runs, regardless of which connection issued it, plus the standard
+//! ODBC `SQL_ATTR_TRACE`/`SQL_ATTR_TRACEFILE` file-tracing story and an
+//! `FURBALL_TRACE=1` escape hatch for ad-hoc debugging.
+
+use crate::handle::TraceEvent;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TRACE_FILE: Mutex<Option<String>> = Mutex::new(None);
+static CALLBACK: Mutex<Option<fn(&TraceEvent)>> = Mutex::new(None);
+static ENV_CHECKED: AtomicBool = AtomicBool::new(false);
+static NEXT_STMT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A process-wide, monotonically increasing id assigned to each `Statement`
+/// at `SQLAllocHandle` time, so traced `Query` events can be correlated back
+/// to the statement that issued them.
+pub fn next_stmt_id() -> u64 {
+    NEXT_STMT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+fn check_env_once() {
+    if ENV_CHECKED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    // `FURBALL_TRACE=stderr` explicitly selects stderr (also the fallback
+    // below when nothing else is configured); any other non-empty,
+    // non-boolean-"0" value is a file path to log to; "1"/"true" just turn
+    // tracing on without picking a sink, falling back to stderr the same way.
+    match std::env::var("FURBALL_TRACE") {
+        Ok(v) if v == "0" || v.is_empty() => {}
+        Ok(v) if v == "stderr" || v == "1" || v.eq_ignore_ascii_case("true") => {
+            ENABLED.store(true, Ordering::Relaxed);
+        }
+        Ok(path) => {
+            ENABLED.store(true, Ordering::Relaxed);
+            *TRACE_FILE.lock().unwrap() = Some(path);
+        }
+        Err(_) => {}
+    }
+}
+
+/// SQL_ATTR_TRACE; mirrors SQL_OPT_TRACE_ON (1) turning tracing on.
+pub fn set_trace(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// SQL_ATTR_TRACEFILE; path written to on every traced event.
+pub fn set_tracefile(path: String) {
+    *TRACE_FILE.lock().unwrap() = Some(path);
+}
+
+/// Registers an in-process sink for every traced event, so an embedder can
+/// capture them directly instead of only through a trace file. Only one
+/// callback may be registered at a time; a later call replaces the former.
+pub fn furball_set_trace_callback(cb: fn(&TraceEvent)) {
+    *CALLBACK.lock().unwrap() = Some(cb);
+}
+
+/// Fires `event` to whatever sinks are configured. A single atomic load when
+/// tracing is off, so instrumented call sites cost nothing in the common
+/// case.
+pub fn emit(event: &TraceEvent) {
+    check_env_once();
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let callback = *CALLBACK.lock().unwrap();
+    if let Some(cb) = callback {
+        cb(event);
+    }
+    if let Some(path) = TRACE_FILE.lock().unwrap().as_ref() {
+        if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(f, "{}", format_event(event));
+        }
+    } else if callback.is_none() {
+        // FURBALL_TRACE=1 with no file and no callback registered: fall back
+        // to stderr so the env var is useful on its own.
+        eprintln!("{}", format_event(event));
+    }
+}
+
+fn format_event(event: &TraceEvent) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match event {
+        TraceEvent::Connect {
+            server,
+            database,
+            conn_str,
+        } => format!(
+            "[{}] connect server={} database={} conn_str={}",
+            now, server, database, conn_str
+        ),
+        TraceEvent::Disconnect { server, database } => {
+            format!("[{}] disconnect server={} database={}", now, server, database)
+        }
+        TraceEvent::Query {
+            server,
+            database,
+            stmt_id,
+            sql,
+            elapsed,
+            result,
+        } => format!(
+            "[{}] stmt#{} {}@{} ({:?}): {} -> {}",
+            now,
+            stmt_id,
+            server,
+            database,
+            elapsed,
+            sql,
+            match result {
+                Ok(rows) => format!("ok ({} rows)", rows),
+                Err(msg) => format!("error: {}", msg),
+            }
+        ),
+        TraceEvent::Transaction {
+            server,
+            database,
+            kind,
+        } => format!("[{}] {} {}@{}", now, kind, server, database),
+    }
+}